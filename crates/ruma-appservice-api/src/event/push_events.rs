@@ -7,9 +7,10 @@ pub mod v1 {
     //!
     //! [spec]: https://spec.matrix.org/latest/application-service-api/#put_matrixappv1transactionstxnid
 
-    use std::borrow::Cow;
-    #[cfg(feature = "unstable-msc3202")]
-    use std::collections::BTreeMap;
+    use std::{
+        borrow::Cow,
+        collections::{BTreeMap, BTreeSet, VecDeque},
+    };
 
     #[cfg(feature = "unstable-msc3202")]
     use js_int::UInt;
@@ -19,7 +20,7 @@ pub mod v1 {
         api::{request, response, Metadata},
         metadata,
         serde::{from_raw_json_value, JsonObject, Raw},
-        OwnedTransactionId,
+        OwnedRoomId, OwnedTransactionId, TransactionId,
     };
     #[cfg(feature = "unstable-msc3202")]
     use ruma_common::{OneTimeKeyAlgorithm, OwnedDeviceId};
@@ -119,6 +120,25 @@ pub mod v1 {
                 to_device: Vec::new(),
             }
         }
+
+        /// Group the events of this transaction by room, preserving their order.
+        ///
+        /// Events without a valid `room_id` field are ignored.
+        ///
+        /// Since the homeserver retries a transaction with the same `txn_id` until it succeeds,
+        /// application services should only process the events of a given `txn_id` once, for
+        /// example with [`ProcessedTransactions`].
+        pub fn events_by_room(&self) -> BTreeMap<OwnedRoomId, Vec<&Raw<AnyTimelineEvent>>> {
+            let mut events_by_room = BTreeMap::<_, Vec<_>>::new();
+
+            for event in &self.events {
+                if let Ok(Some(room_id)) = event.get_field::<OwnedRoomId>("room_id") {
+                    events_by_room.entry(room_id).or_default().push(event);
+                }
+            }
+
+            events_by_room
+        }
     }
 
     impl Response {
@@ -128,6 +148,61 @@ pub mod v1 {
         }
     }
 
+    /// The IDs of the latest transactions processed by an application service.
+    ///
+    /// The homeserver retries a transaction with the same `txn_id` until the application service
+    /// responds successfully, so the `txn_id` is the idempotency key of the transaction. This can
+    /// be used to skip transactions that were already processed, to process their events
+    /// exactly once.
+    ///
+    /// Only the given number of latest transaction IDs are remembered, since the homeserver only
+    /// retries the transactions that were not acknowledged yet.
+    #[derive(Clone, Debug)]
+    pub struct ProcessedTransactions {
+        /// The maximum number of transaction IDs to remember.
+        capacity: usize,
+
+        /// The remembered transaction IDs, from the oldest to the newest.
+        order: VecDeque<OwnedTransactionId>,
+
+        /// The remembered transaction IDs, for fast lookup.
+        txn_ids: BTreeSet<OwnedTransactionId>,
+    }
+
+    impl ProcessedTransactions {
+        /// Creates an empty `ProcessedTransactions` remembering at most `capacity` transaction IDs.
+        pub fn new(capacity: usize) -> Self {
+            Self { capacity, order: VecDeque::new(), txn_ids: BTreeSet::new() }
+        }
+
+        /// Whether the transaction with the given ID was already processed.
+        pub fn is_processed(&self, txn_id: &TransactionId) -> bool {
+            self.txn_ids.contains(txn_id)
+        }
+
+        /// Mark the transaction with the given ID as processed.
+        ///
+        /// This should be called once all the data of the transaction was processed successfully,
+        /// before responding to the homeserver.
+        ///
+        /// Returns `false` if the transaction was already marked as processed.
+        pub fn mark_processed(&mut self, txn_id: OwnedTransactionId) -> bool {
+            if self.capacity == 0 || !self.txn_ids.insert(txn_id.clone()) {
+                return false;
+            }
+
+            self.order.push_back(txn_id);
+
+            if self.order.len() > self.capacity {
+                if let Some(oldest) = self.order.pop_front() {
+                    self.txn_ids.remove(&oldest);
+                }
+            }
+
+            true
+        }
+    }
+
     /// Information on E2E device updates.
     #[derive(Clone, Debug, Default, Deserialize, Serialize)]
     #[cfg_attr(not(ruma_unstable_exhaustive_types), non_exhaustive)]
@@ -261,7 +336,7 @@ pub mod v1 {
         use ruma_events::receipt::ReceiptType;
         use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
-        use super::EphemeralData;
+        use super::{EphemeralData, ProcessedTransactions, Request};
 
         #[cfg(feature = "client")]
         #[test]
@@ -307,6 +382,67 @@ pub mod v1 {
             );
         }
 
+        #[test]
+        fn events_by_room() {
+            let event = |event_id: &str, room_id: &str| {
+                from_json_value(json!({
+                    "type": "m.room.message",
+                    "event_id": event_id,
+                    "origin_server_ts": 1,
+                    "room_id": room_id,
+                    "sender": "@user:example.com",
+                    "content": {
+                        "body": "test",
+                        "msgtype": "m.text",
+                    },
+                }))
+                .unwrap()
+            };
+            let first_room_id = room_id!("!first:example.com");
+            let second_room_id = room_id!("!second:example.com");
+
+            let request = Request::new(
+                "any_txn_id".into(),
+                vec![
+                    event("$1", first_room_id.as_str()),
+                    event("$2", second_room_id.as_str()),
+                    event("$3", first_room_id.as_str()),
+                ],
+            );
+            let events_by_room = request.events_by_room();
+
+            assert_eq!(events_by_room.len(), 2);
+            let first_room_events = events_by_room
+                .get(first_room_id)
+                .unwrap()
+                .iter()
+                .map(|event| event.get_field::<String>("event_id").unwrap().unwrap())
+                .collect::<Vec<_>>();
+            assert_eq!(first_room_events, ["$1", "$3"]);
+            assert_eq!(events_by_room.get(second_room_id).unwrap().len(), 1);
+        }
+
+        #[test]
+        fn processed_transactions() {
+            let mut processed = ProcessedTransactions::new(2);
+            let request = Request::new("txn_1".into(), Vec::new());
+
+            assert!(!processed.is_processed(&request.txn_id));
+            assert!(processed.mark_processed(request.txn_id.clone()));
+            assert!(processed.is_processed(&request.txn_id));
+
+            // A retry of the same transaction is detected.
+            assert!(!processed.mark_processed(request.txn_id.clone()));
+
+            assert!(processed.mark_processed("txn_2".into()));
+            assert!(processed.mark_processed("txn_3".into()));
+
+            // The oldest transaction ID is forgotten.
+            assert!(!processed.is_processed("txn_1".into()));
+            assert!(processed.is_processed("txn_2".into()));
+            assert!(processed.is_processed("txn_3".into()));
+        }
+
         #[test]
         fn serde_ephemeral_data() {
             let room_id = room_id!("!jEsUZKDJdhlrceRyVU:server.local");
@@ -7,6 +7,8 @@
 
 #![warn(missing_docs)]
 
+use regex::RegexSet;
+use ruma_common::{RoomAliasId, RoomId, UserId};
 use serde::{Deserialize, Serialize};
 
 pub mod event;
@@ -59,6 +61,109 @@ impl Namespaces {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Compile the regular expressions of these namespaces.
+    ///
+    /// Returns an error if one of the regular expressions is invalid.
+    pub fn compile(&self) -> Result<CompiledNamespaces, NamespaceRegexError> {
+        Ok(CompiledNamespaces {
+            users: CompiledNamespace::new(&self.users)?,
+            aliases: CompiledNamespace::new(&self.aliases)?,
+            rooms: CompiledNamespace::new(&self.rooms)?,
+        })
+    }
+}
+
+/// The compiled regular expressions of a list of [`Namespace`]s.
+///
+/// A value is in the namespace if it matches one of the regular expressions entirely.
+#[derive(Clone, Debug)]
+pub struct CompiledNamespace {
+    /// The regular expressions of the exclusive namespaces.
+    exclusive: RegexSet,
+
+    /// The regular expressions of the non-exclusive namespaces.
+    non_exclusive: RegexSet,
+}
+
+impl CompiledNamespace {
+    /// Compile the regular expressions of the given namespaces.
+    ///
+    /// Returns an error if one of the regular expressions is invalid.
+    pub fn new(namespaces: &[Namespace]) -> Result<Self, NamespaceRegexError> {
+        let (exclusive, non_exclusive): (Vec<_>, Vec<_>) =
+            namespaces.iter().partition(|namespace| namespace.exclusive);
+        let regex_set = |namespaces: Vec<&Namespace>| {
+            RegexSet::new(
+                namespaces.into_iter().map(|namespace| format!("^(?:{})$", namespace.regex)),
+            )
+            .map_err(NamespaceRegexError)
+        };
+
+        Ok(Self { exclusive: regex_set(exclusive)?, non_exclusive: regex_set(non_exclusive)? })
+    }
+
+    /// Whether the given value is in one of the namespaces.
+    pub fn is_match(&self, value: &str) -> bool {
+        self.exclusive.is_match(value) || self.non_exclusive.is_match(value)
+    }
+
+    /// Whether the given value is in one of the exclusive namespaces.
+    pub fn is_exclusive_match(&self, value: &str) -> bool {
+        self.exclusive.is_match(value)
+    }
+}
+
+/// An error encountered when compiling the regular expressions of [`Namespace`]s.
+#[derive(Debug, thiserror::Error)]
+#[error("invalid namespace regular expression: {0}")]
+pub struct NamespaceRegexError(regex::Error);
+
+/// The compiled regular expressions of [`Namespaces`].
+///
+/// To create an instance of this type, use [`Namespaces::compile()`].
+#[derive(Clone, Debug)]
+#[allow(clippy::exhaustive_structs)]
+pub struct CompiledNamespaces {
+    /// The namespaces of users.
+    pub users: CompiledNamespace,
+
+    /// The namespaces of room aliases.
+    pub aliases: CompiledNamespace,
+
+    /// The namespaces of room IDs.
+    pub rooms: CompiledNamespace,
+}
+
+impl CompiledNamespaces {
+    /// Whether the given user ID is in the namespaces of the application service.
+    pub fn is_user_in_namespace(&self, user_id: &UserId) -> bool {
+        self.users.is_match(user_id.as_str())
+    }
+
+    /// Whether the given user ID is in the exclusive namespaces of the application service.
+    ///
+    /// Other application services and users should not be able to use this user ID.
+    pub fn is_user_in_exclusive_namespace(&self, user_id: &UserId) -> bool {
+        self.users.is_exclusive_match(user_id.as_str())
+    }
+
+    /// Whether the given room alias is in the namespaces of the application service.
+    pub fn is_room_alias_in_namespace(&self, alias: &RoomAliasId) -> bool {
+        self.aliases.is_match(alias.as_str())
+    }
+
+    /// Whether the given room alias is in the exclusive namespaces of the application service.
+    ///
+    /// Other application services and users should not be able to use this room alias.
+    pub fn is_room_alias_in_exclusive_namespace(&self, alias: &RoomAliasId) -> bool {
+        self.aliases.is_exclusive_match(alias.as_str())
+    }
+
+    /// Whether the given room ID is in the namespaces of the application service.
+    pub fn is_room_id_in_namespace(&self, room_id: &RoomId) -> bool {
+        self.rooms.is_match(room_id.as_str())
+    }
 }
 
 /// Information required in the registration yaml file that a homeserver needs.
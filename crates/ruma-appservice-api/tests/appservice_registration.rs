@@ -1,5 +1,6 @@
 use assert_matches2::assert_matches;
-use ruma_appservice_api::Registration;
+use ruma_appservice_api::{Namespace, Namespaces, Registration};
+use ruma_common::{room_alias_id, room_id, user_id};
 
 #[test]
 fn registration_deserialization() {
@@ -76,3 +77,58 @@ fn registration_with_missing_url() {
         "#;
     serde_yaml::from_str::<Registration>(registration_config).unwrap_err();
 }
+
+#[test]
+fn registration_compiled_namespaces() {
+    let registration_config = r##"
+        id: "IRC Bridge"
+        url: null
+        as_token: "30c05ae90a248a4188e620216fa72e349803310ec83e2a77b34fe90be6081f46"
+        hs_token: "312df522183efd404ec1cd22d2ffa4bbc76a8c1ccf541dd692eef281356bb74e"
+        sender_localpart: "_irc_bot"
+        namespaces:
+          users:
+            - exclusive: true
+              regex: "@_irc_bridge_.*:example\\.org"
+            - exclusive: false
+              regex: "@irc_.*:example\\.org"
+          aliases:
+            - exclusive: false
+              regex: "#_irc_bridge_.*"
+        "##;
+    let registration: Registration = serde_yaml::from_str(registration_config).unwrap();
+    let namespaces = registration.namespaces.compile().unwrap();
+
+    let bridged_user = user_id!("@_irc_bridge_alice:example.org");
+    assert!(namespaces.is_user_in_namespace(bridged_user));
+    assert!(namespaces.is_user_in_exclusive_namespace(bridged_user));
+
+    let irc_user = user_id!("@irc_bob:example.org");
+    assert!(namespaces.is_user_in_namespace(irc_user));
+    assert!(!namespaces.is_user_in_exclusive_namespace(irc_user));
+
+    // The regex must match the whole user ID.
+    let other_user = user_id!("@_irc_bridge_carl:example.org.evil");
+    assert!(!namespaces.is_user_in_namespace(other_user));
+
+    let alias = room_alias_id!("#_irc_bridge_matrix:example.org");
+    assert!(namespaces.is_room_alias_in_namespace(alias));
+    assert!(!namespaces.is_room_alias_in_exclusive_namespace(alias));
+
+    assert!(!namespaces.is_room_id_in_namespace(room_id!("!room:example.org")));
+}
+
+#[test]
+fn compile_namespaces_with_perl_classes() {
+    let mut namespaces = Namespaces::new();
+    namespaces.users = vec![
+        Namespace::new(true, r"@_irc_\w+:example\.org".to_owned()),
+        Namespace::new(false, r"(?i)@telegram_\d+:example\.org".to_owned()),
+    ];
+    let namespaces = namespaces.compile().unwrap();
+
+    assert!(namespaces.is_user_in_exclusive_namespace(user_id!("@_irc_alice:example.org")));
+    assert!(!namespaces.is_user_in_namespace(user_id!("@_irc_:example.org")));
+    assert!(namespaces.is_user_in_namespace(user_id!("@TELEGRAM_12345:example.org")));
+    assert!(!namespaces.is_user_in_namespace(user_id!("@telegram_bob:example.org")));
+}
@@ -14,7 +14,7 @@ pub mod v1 {
     use ruma_common::{
         api::{request, response, Metadata},
         http_headers::ContentDisposition,
-        media::Method,
+        media::{recommended_thumbnail_size, Method},
         metadata, IdParseError, MxcUri, OwnedServerName,
     };
 
@@ -126,6 +126,25 @@ pub mod v1 {
 
             Ok(Self::new(media_id.to_owned(), server_name.to_owned(), width, height))
         }
+
+        /// Creates a new `Request` with the given URI and resizing method, using the smallest
+        /// thumbnail size recommended by the spec that is at least as large as the desired width
+        /// and height.
+        ///
+        /// Servers should pre-generate thumbnails with the recommended sizes, so the request is
+        /// more likely to hit a cache. If the desired size is larger than all the recommended
+        /// sizes, it is used as-is.
+        pub fn from_uri_with_recommended_size(
+            uri: &MxcUri,
+            method: Method,
+            width: UInt,
+            height: UInt,
+        ) -> Result<Self, IdParseError> {
+            let (width, height) =
+                recommended_thumbnail_size(&method, width, height).unwrap_or((width, height));
+
+            Ok(Self { method: Some(method), ..Self::from_uri(uri, width, height)? })
+        }
     }
 
     impl Response {
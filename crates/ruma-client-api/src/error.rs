@@ -21,6 +21,7 @@ use web_time::{Duration, SystemTime};
 
 use crate::{
     http_headers::{http_date_to_system_time, system_time_to_http_date},
+    uiaa::UiaaResponse,
     PrivOwnedStr,
 };
 
@@ -1144,7 +1145,8 @@ impl TryFrom<&RetryAfter> for http::HeaderValue {
     }
 }
 
-/// Extension trait for `FromHttpResponseError<ruma_client_api::Error>`.
+/// Extension trait for `FromHttpResponseError<ruma_client_api::Error>` and
+/// `FromHttpResponseError<ruma_client_api::uiaa::UiaaResponse>`.
 pub trait FromHttpResponseErrorExt {
     /// If `self` is a server error in the `errcode` + `error` format expected
     /// for client-server API endpoints, returns the error kind (`errcode`).
@@ -1157,6 +1159,12 @@ impl FromHttpResponseErrorExt for FromHttpResponseError<Error> {
     }
 }
 
+impl FromHttpResponseErrorExt for FromHttpResponseError<UiaaResponse> {
+    fn error_kind(&self) -> Option<&ErrorKind> {
+        as_variant!(self, Self::Server)?.error_kind()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use assert_matches2::assert_matches;
@@ -15,6 +15,7 @@ pub mod v3 {
     use ruma_common::{
         api::{request, response, Metadata},
         http_headers::ContentDisposition,
+        media::recommended_thumbnail_size,
         metadata, IdParseError, MxcUri, OwnedServerName,
     };
 
@@ -162,6 +163,25 @@ pub mod v3 {
 
             Ok(Self::new(media_id.to_owned(), server_name.to_owned(), width, height))
         }
+
+        /// Creates a new `Request` with the given url and resizing method, using the smallest
+        /// thumbnail size recommended by the spec that is at least as large as the desired width
+        /// and height.
+        ///
+        /// Servers should pre-generate thumbnails with the recommended sizes, so the request is
+        /// more likely to hit a cache. If the desired size is larger than all the recommended
+        /// sizes, it is used as-is.
+        pub fn from_url_with_recommended_size(
+            url: &MxcUri,
+            method: Method,
+            width: UInt,
+            height: UInt,
+        ) -> Result<Self, IdParseError> {
+            let (width, height) =
+                recommended_thumbnail_size(&method, width, height).unwrap_or((width, height));
+
+            Ok(Self { method: Some(method), ..Self::from_url(url, width, height)? })
+        }
     }
 
     impl Response {
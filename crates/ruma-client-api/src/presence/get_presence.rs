@@ -12,8 +12,8 @@ pub mod v3 {
     use ruma_common::{
         api::{request, response, Metadata},
         metadata,
-        presence::PresenceState,
-        OwnedUserId,
+        presence::{LastActive, PresenceState},
+        MilliSecondsSinceUnixEpoch, OwnedUserId,
     };
 
     const METADATA: Metadata = metadata! {
@@ -69,5 +69,12 @@ pub mod v3 {
         pub fn new(presence: PresenceState) -> Self {
             Self { presence, status_msg: None, currently_active: None, last_active_ago: None }
         }
+
+        /// Computes when the user was last active.
+        ///
+        /// `received_at` should be the time when this response was received.
+        pub fn last_active(&self, received_at: MilliSecondsSinceUnixEpoch) -> LastActive {
+            LastActive::new(self.currently_active, self.last_active_ago, received_at)
+        }
     }
 }
@@ -2,8 +2,9 @@
 //!
 //! [uiaa]: https://spec.matrix.org/latest/client-server-api/#user-interactive-authentication-api
 
-use std::{borrow::Cow, fmt};
+use std::{borrow::Cow, collections::BTreeMap, fmt};
 
+use as_variant::as_variant;
 use bytes::BufMut;
 use ruma_common::{
     api::{error::IntoHttpError, EndpointError, OutgoingResponse},
@@ -20,7 +21,7 @@ use serde_json::{
 };
 
 use crate::{
-    error::{Error as MatrixError, StandardErrorBody},
+    error::{Error as MatrixError, ErrorKind, StandardErrorBody},
     PrivOwnedStr,
 };
 
@@ -647,6 +648,174 @@ impl UiaaInfo {
     pub fn new(flows: Vec<AuthFlow>) -> Self {
         Self { flows, completed: Vec::new(), params: None, session: None, auth_error: None }
     }
+
+    /// The progress of the authentication described by this `UiaaInfo`.
+    fn progress(&self) -> UiaaProgress<'_> {
+        UiaaProgress::new(&self.flows, &self.completed, self.session.as_deref())
+    }
+
+    /// Creates a request to get the fallback web page for the given stage of this session.
+    ///
+    /// Returns `None` if the homeserver didn't provide a session.
+    pub fn fallback_page_request(
+        &self,
+        auth_type: AuthType,
+    ) -> Option<get_uiaa_fallback_page::v3::Request> {
+        self.progress().fallback_page_request(auth_type)
+    }
+
+    /// Returns the next stage to complete, if any.
+    ///
+    /// This is the next stage of the first flow that starts with the `completed` stages and is not
+    /// finished yet. Returns `None` if one of the flows was completed, or if no such flow exists.
+    pub fn next_stage(&self) -> Option<&AuthType> {
+        self.progress().next_stage()
+    }
+
+    /// Whether all the stages of one of the flows were completed.
+    pub fn is_completed(&self) -> bool {
+        self.progress().is_completed()
+    }
+
+    /// Deserializes the parameters of the given stage, if any.
+    ///
+    /// The parameters of the stages defined in the specification are available as
+    /// [`ReCaptchaParams`] and [`TermsParams`].
+    ///
+    /// Returns `Ok(None)` if the homeserver didn't provide parameters for this stage, and an error
+    /// if the parameters could not be deserialized.
+    pub fn params_for<T: DeserializeOwned>(
+        &self,
+        auth_type: &AuthType,
+    ) -> serde_json::Result<Option<T>> {
+        let Some(params) = &self.params else {
+            return Ok(None);
+        };
+
+        let mut params: BTreeMap<String, Box<RawJsonValue>> = serde_json::from_str(params.get())?;
+        params
+            .remove(auth_type.as_str())
+            .map(|params| serde_json::from_str(params.get()))
+            .transpose()
+    }
+}
+
+/// The state of a User-Interactive Authentication session, kept across the retries of a request.
+///
+/// It is updated with the [`UiaaInfo`] returned by the homeserver after each attempt, and
+/// remembers the completed stages and the session key even if a response omits them.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(not(ruma_unstable_exhaustive_types), non_exhaustive)]
+pub struct UiaaSession {
+    /// The session key given by the homeserver, if any.
+    pub session: Option<String>,
+
+    /// The authentication flows available for the request.
+    pub flows: Vec<AuthFlow>,
+
+    /// The stages completed during this session.
+    pub completed: Vec<AuthType>,
+}
+
+impl UiaaSession {
+    /// Creates a new empty `UiaaSession`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The progress of the authentication described by this `UiaaSession`.
+    fn progress(&self) -> UiaaProgress<'_> {
+        UiaaProgress::new(&self.flows, &self.completed, self.session.as_deref())
+    }
+
+    /// Updates this session with the `UiaaInfo` returned by the homeserver.
+    ///
+    /// If the homeserver returned a different session key, the completed stages are reset.
+    /// Otherwise, the newly completed stages are added to the ones that were already completed.
+    pub fn update(&mut self, info: &UiaaInfo) {
+        if info.session.is_some() && info.session != self.session {
+            self.session.clone_from(&info.session);
+            self.completed.clear();
+        }
+
+        if !info.flows.is_empty() {
+            self.flows.clone_from(&info.flows);
+        }
+
+        for stage in &info.completed {
+            if !self.completed.contains(stage) {
+                self.completed.push(stage.clone());
+            }
+        }
+    }
+
+    /// Returns the next stage to complete, if any.
+    ///
+    /// See [`UiaaInfo::next_stage()`] for details.
+    pub fn next_stage(&self) -> Option<&AuthType> {
+        self.progress().next_stage()
+    }
+
+    /// Whether all the stages of one of the flows were completed.
+    pub fn is_completed(&self) -> bool {
+        self.progress().is_completed()
+    }
+
+    /// Creates a request to get the fallback web page for the given stage of this session.
+    ///
+    /// Returns `None` if the homeserver didn't provide a session.
+    pub fn fallback_page_request(
+        &self,
+        auth_type: AuthType,
+    ) -> Option<get_uiaa_fallback_page::v3::Request> {
+        self.progress().fallback_page_request(auth_type)
+    }
+}
+
+impl From<&UiaaInfo> for UiaaSession {
+    fn from(info: &UiaaInfo) -> Self {
+        let mut session = Self::new();
+        session.update(info);
+        session
+    }
+}
+
+/// The progress of a User-Interactive Authentication, shared by [`UiaaInfo`] and [`UiaaSession`].
+struct UiaaProgress<'a> {
+    /// The authentication flows available for the request.
+    flows: &'a [AuthFlow],
+
+    /// The stages that were completed.
+    completed: &'a [AuthType],
+
+    /// The session key given by the homeserver, if any.
+    session: Option<&'a str>,
+}
+
+impl<'a> UiaaProgress<'a> {
+    fn new(flows: &'a [AuthFlow], completed: &'a [AuthType], session: Option<&'a str>) -> Self {
+        Self { flows, completed, session }
+    }
+
+    fn next_stage(&self) -> Option<&'a AuthType> {
+        if self.is_completed() {
+            return None;
+        }
+
+        self.flows.iter().find_map(|flow| flow.next_stage(self.completed))
+    }
+
+    fn is_completed(&self) -> bool {
+        self.flows.iter().any(|flow| flow.stages == self.completed)
+    }
+
+    fn fallback_page_request(
+        &self,
+        auth_type: AuthType,
+    ) -> Option<get_uiaa_fallback_page::v3::Request> {
+        let session = self.session?.to_owned();
+        Some(get_uiaa_fallback_page::v3::Request::new(auth_type, session))
+    }
 }
 
 /// Description of steps required to authenticate via the User-Interactive Authentication API.
@@ -665,6 +834,81 @@ impl AuthFlow {
     pub fn new(stages: Vec<AuthType>) -> Self {
         Self { stages }
     }
+
+    /// Returns the next stage of this flow given the `completed` stages, if any.
+    ///
+    /// Returns `None` if all the stages of this flow were completed, or if the `completed` stages
+    /// are not the first stages of this flow.
+    pub fn next_stage(&self, completed: &[AuthType]) -> Option<&AuthType> {
+        self.stages.strip_prefix(completed)?.first()
+    }
+}
+
+/// Parameters for Google ReCaptcha 2.0 authentication (`m.login.recaptcha`).
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(not(ruma_unstable_exhaustive_types), non_exhaustive)]
+pub struct ReCaptchaParams {
+    /// The public key to use to display the ReCaptcha.
+    pub public_key: String,
+}
+
+impl ReCaptchaParams {
+    /// Creates a new `ReCaptchaParams` with the given public key.
+    pub fn new(public_key: String) -> Self {
+        Self { public_key }
+    }
+}
+
+/// Parameters for terms of service authentication (`m.login.terms`).
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[cfg_attr(not(ruma_unstable_exhaustive_types), non_exhaustive)]
+pub struct TermsParams {
+    /// The policies the user must accept, keyed by an arbitrary policy ID.
+    pub policies: BTreeMap<String, PolicyDefinition>,
+}
+
+impl TermsParams {
+    /// Creates a new `TermsParams` with the given policies.
+    pub fn new(policies: BTreeMap<String, PolicyDefinition>) -> Self {
+        Self { policies }
+    }
+}
+
+/// A policy that the user must accept during terms of service authentication.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(not(ruma_unstable_exhaustive_types), non_exhaustive)]
+pub struct PolicyDefinition {
+    /// The version of the policy.
+    pub version: String,
+
+    /// The translations of the policy, keyed by language code.
+    #[serde(flatten)]
+    pub translations: BTreeMap<String, PolicyTranslation>,
+}
+
+impl PolicyDefinition {
+    /// Creates a new `PolicyDefinition` with the given version and translations.
+    pub fn new(version: String, translations: BTreeMap<String, PolicyTranslation>) -> Self {
+        Self { version, translations }
+    }
+}
+
+/// A translation of a policy.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(not(ruma_unstable_exhaustive_types), non_exhaustive)]
+pub struct PolicyTranslation {
+    /// The name of the policy in this language.
+    pub name: String,
+
+    /// The URL of the policy in this language.
+    pub url: String,
+}
+
+impl PolicyTranslation {
+    /// Creates a new `PolicyTranslation` with the given name and URL.
+    pub fn new(name: String, url: String) -> Self {
+        Self { name, url }
+    }
 }
 
 /// Contains either a User-Interactive Authentication API response body or a Matrix error.
@@ -678,6 +922,25 @@ pub enum UiaaResponse {
     MatrixError(MatrixError),
 }
 
+impl UiaaResponse {
+    /// Returns the User-Interactive Authentication API response body, if this is a challenge to
+    /// complete a stage of the authentication.
+    pub fn uiaa_info(&self) -> Option<&UiaaInfo> {
+        as_variant!(self, Self::AuthResponse)
+    }
+
+    /// Returns the error kind (`errcode`) of the response, if any.
+    ///
+    /// For [`UiaaResponse::AuthResponse`], this is the error of the previous authentication
+    /// attempt, if the homeserver returned one.
+    pub fn error_kind(&self) -> Option<&ErrorKind> {
+        match self {
+            Self::AuthResponse(info) => info.auth_error.as_ref().map(|error| &error.kind),
+            Self::MatrixError(error) => error.error_kind(),
+        }
+    }
+}
+
 impl fmt::Display for UiaaResponse {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
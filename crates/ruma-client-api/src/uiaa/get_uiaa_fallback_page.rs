@@ -41,6 +41,29 @@ pub mod v3 {
         pub fn new(auth_type: AuthType, session: String) -> Self {
             Self { auth_type, session }
         }
+
+        /// Builds the URL of the fallback web page, to be opened in a web browser by the user.
+        ///
+        /// Once the user has completed the stage in the web page, the client should retry the
+        /// request with [`AuthData::fallback_acknowledgement()`] and the same session.
+        ///
+        /// [`AuthData::fallback_acknowledgement()`]: crate::uiaa::AuthData::fallback_acknowledgement
+        #[cfg(feature = "client")]
+        pub fn fallback_url(
+            self,
+            homeserver_url: &str,
+            considering: &ruma_common::api::SupportedVersions,
+        ) -> Result<String, ruma_common::api::error::IntoHttpError> {
+            use ruma_common::api::{OutgoingRequest, SendAccessToken};
+
+            let http_request = self.try_into_http_request::<Vec<u8>>(
+                homeserver_url,
+                SendAccessToken::None,
+                considering,
+            )?;
+
+            Ok(http_request.uri().to_string())
+        }
     }
 
     /// Response type for the `authorize_fallback` endpoint.
@@ -149,6 +172,28 @@ pub mod v3 {
 
         use super::Response;
 
+        #[cfg(feature = "client")]
+        #[test]
+        fn fallback_url() {
+            use ruma_common::api::{MatrixVersion, SupportedVersions};
+
+            use super::Request;
+            use crate::uiaa::AuthType;
+
+            let supported = SupportedVersions {
+                versions: [MatrixVersion::V1_1].into(),
+                features: Default::default(),
+            };
+
+            let url = Request::new(AuthType::ReCaptcha, "my session".to_owned())
+                .fallback_url("https://localhost/", &supported)
+                .unwrap();
+            assert_eq!(
+                url,
+                "https://localhost/_matrix/client/v3/auth/m.login.recaptcha/fallback/web?session=my+session"
+            );
+        }
+
         #[cfg(feature = "client")]
         #[test]
         fn incoming_redirect() {
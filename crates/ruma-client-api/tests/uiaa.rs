@@ -4,7 +4,10 @@ use assert_matches2::assert_matches;
 use assign::assign;
 use ruma_client_api::{
     error::ErrorKind,
-    uiaa::{self, AuthData, AuthFlow, AuthType, UiaaInfo, UiaaResponse, UserIdentifier},
+    uiaa::{
+        self, AuthData, AuthFlow, AuthType, ReCaptchaParams, TermsParams, UiaaInfo, UiaaResponse,
+        UiaaSession, UserIdentifier,
+    },
 };
 use ruma_common::api::{EndpointError, OutgoingResponse};
 use serde_json::{
@@ -220,3 +223,161 @@ fn try_uiaa_response_from_http_response() {
         })
     );
 }
+
+#[test]
+fn uiaa_response_error_kind() {
+    let json = serde_json::to_string(&json!({
+        "errcode": "M_FORBIDDEN",
+        "error": "Invalid password",
+        "flows": [
+            {
+                "stages": ["m.login.password"]
+            }
+        ],
+        "session": "xxxxxx"
+    }))
+    .unwrap();
+    let http_response = http::Response::builder()
+        .status(http::StatusCode::UNAUTHORIZED)
+        .body(json.as_bytes())
+        .unwrap();
+
+    let response = UiaaResponse::from_http_response(http_response);
+    let info = response.uiaa_info().unwrap();
+    assert_eq!(info.session.as_deref(), Some("xxxxxx"));
+    assert_matches!(response.error_kind(), Some(ErrorKind::Forbidden { .. }));
+
+    let json = serde_json::to_string(&json!({
+        "errcode": "M_USER_DEACTIVATED",
+        "error": "This account has been deactivated",
+    }))
+    .unwrap();
+    let http_response = http::Response::builder()
+        .status(http::StatusCode::FORBIDDEN)
+        .body(json.as_bytes())
+        .unwrap();
+
+    let response = UiaaResponse::from_http_response(http_response);
+    assert_matches!(response.uiaa_info(), None);
+    assert_matches!(response.error_kind(), Some(ErrorKind::UserDeactivated));
+}
+
+#[test]
+fn uiaa_info_fallback_page_request() {
+    let mut info = UiaaInfo::new(vec![AuthFlow::new(vec![AuthType::ReCaptcha])]);
+    assert!(info.fallback_page_request(AuthType::ReCaptcha).is_none());
+
+    info.session = Some("xxxxxx".to_owned());
+    let request = info.fallback_page_request(AuthType::ReCaptcha).unwrap();
+    assert_eq!(request.auth_type, AuthType::ReCaptcha);
+    assert_eq!(request.session, "xxxxxx");
+}
+
+#[test]
+fn uiaa_info_next_stage() {
+    let mut info = UiaaInfo::new(vec![
+        AuthFlow::new(vec![AuthType::ReCaptcha, AuthType::EmailIdentity]),
+        AuthFlow::new(vec![AuthType::ReCaptcha, AuthType::Msisdn, AuthType::Terms]),
+    ]);
+    assert_eq!(info.next_stage(), Some(&AuthType::ReCaptcha));
+
+    info.completed = vec![AuthType::ReCaptcha];
+    assert_eq!(info.next_stage(), Some(&AuthType::EmailIdentity));
+
+    info.completed = vec![AuthType::ReCaptcha, AuthType::Msisdn];
+    assert_eq!(info.next_stage(), Some(&AuthType::Terms));
+
+    info.completed = vec![AuthType::ReCaptcha, AuthType::EmailIdentity];
+    assert_eq!(info.next_stage(), None);
+
+    info.completed = vec![AuthType::Password];
+    assert_eq!(info.next_stage(), None);
+}
+
+#[test]
+fn uiaa_info_next_stage_completed_flow() {
+    let mut info = UiaaInfo::new(vec![
+        AuthFlow::new(vec![AuthType::ReCaptcha, AuthType::Terms, AuthType::EmailIdentity]),
+        AuthFlow::new(vec![AuthType::ReCaptcha, AuthType::Terms]),
+    ]);
+
+    info.completed = vec![AuthType::ReCaptcha];
+    assert!(!info.is_completed());
+    assert_eq!(info.next_stage(), Some(&AuthType::Terms));
+
+    info.completed = vec![AuthType::ReCaptcha, AuthType::Terms];
+    assert!(info.is_completed());
+    assert_eq!(info.next_stage(), None);
+}
+
+#[test]
+fn uiaa_info_params_for() {
+    let json = json!({
+        "flows": [{ "stages": ["m.login.recaptcha", "m.login.terms"] }],
+        "params": {
+            "m.login.recaptcha": { "public_key": "6Le31_kSAAAAAK-54VKccKamtr-MFA_3WS1d_fGV" },
+            "m.login.terms": {
+                "policies": {
+                    "privacy_policy": {
+                        "version": "1.2",
+                        "en": {
+                            "name": "Privacy Policy",
+                            "url": "https://example.org/somewhere/privacy-1.2-en.html"
+                        },
+                        "fr": {
+                            "name": "Politique de confidentialité",
+                            "url": "https://example.org/somewhere/privacy-1.2-fr.html"
+                        }
+                    }
+                }
+            }
+        },
+        "session": "xxxxxx"
+    });
+    let info = from_json_value::<UiaaInfo>(json).unwrap();
+
+    let recaptcha = info.params_for::<ReCaptchaParams>(&AuthType::ReCaptcha).unwrap().unwrap();
+    assert_eq!(recaptcha.public_key, "6Le31_kSAAAAAK-54VKccKamtr-MFA_3WS1d_fGV");
+
+    let terms = info.params_for::<TermsParams>(&AuthType::Terms).unwrap().unwrap();
+    let policy = &terms.policies["privacy_policy"];
+    assert_eq!(policy.version, "1.2");
+    assert_eq!(policy.translations.len(), 2);
+    assert_eq!(policy.translations["fr"].name, "Politique de confidentialité");
+
+    assert_matches!(info.params_for::<TermsParams>(&AuthType::Password), Ok(None));
+    info.params_for::<TermsParams>(&AuthType::ReCaptcha).unwrap_err();
+}
+
+#[test]
+fn uiaa_session_tracks_completed_stages() {
+    let flows = vec![AuthFlow::new(vec![AuthType::ReCaptcha, AuthType::Terms, AuthType::Dummy])];
+
+    let mut info = UiaaInfo::new(flows.clone());
+    info.session = Some("xxxxxx".to_owned());
+    let mut session = UiaaSession::from(&info);
+    assert_eq!(session.next_stage(), Some(&AuthType::ReCaptcha));
+
+    info.completed = vec![AuthType::ReCaptcha];
+    session.update(&info);
+    assert_eq!(session.next_stage(), Some(&AuthType::Terms));
+
+    // A response to a failed attempt without the completed stages doesn't lose them.
+    let mut info = UiaaInfo::new(flows.clone());
+    info.session = Some("xxxxxx".to_owned());
+    session.update(&info);
+    assert_eq!(session.completed, [AuthType::ReCaptcha]);
+
+    info.completed = vec![AuthType::ReCaptcha, AuthType::Terms, AuthType::Dummy];
+    session.update(&info);
+    assert!(session.is_completed());
+    assert_eq!(session.next_stage(), None);
+
+    // A new session starts over.
+    let mut info = UiaaInfo::new(flows);
+    info.session = Some("yyyyyy".to_owned());
+    session.update(&info);
+    assert_eq!(session.session.as_deref(), Some("yyyyyy"));
+    assert!(session.completed.is_empty());
+    assert_eq!(session.fallback_page_request(AuthType::ReCaptcha).unwrap().session, "yyyyyy");
+}
@@ -12,10 +12,11 @@
 //!
 //! [apis]: https://spec.matrix.org/latest/#matrix-apis
 
-use std::{convert::TryInto as _, error::Error as StdError};
+use std::{borrow::Cow, convert::TryInto as _, error::Error as StdError};
 
 use as_variant::as_variant;
 use bytes::BufMut;
+use percent_encoding::percent_decode_str;
 /// Generates [`OutgoingRequest`] and [`IncomingRequest`] implementations.
 ///
 /// The `OutgoingRequest` impl is on the `Request` type this attribute is used on. It is
@@ -426,6 +427,39 @@ pub trait IncomingRequest: Sized {
     where
         B: AsRef<[u8]>,
         S: AsRef<str>;
+
+    /// Tries to turn the given `http::Request` into this request type, extracting the path
+    /// arguments from the request's path according to [`METADATA`](Self::METADATA).
+    ///
+    /// Fails if the method of the request or its path don't match the endpoint, or if the path
+    /// arguments can't be percent-decoded.
+    ///
+    /// This allows server implementations to build requests without having their router extract
+    /// the path arguments.
+    fn try_from_http_request_with_path<B>(
+        req: http::Request<B>,
+    ) -> Result<Self, FromHttpRequestError>
+    where
+        B: AsRef<[u8]>,
+    {
+        if req.method() != Self::METADATA.method {
+            return Err(FromHttpRequestError::MethodMismatch {
+                expected: Self::METADATA.method,
+                received: req.method().clone(),
+            });
+        }
+
+        let path_args = Self::METADATA
+            .match_path(req.uri().path())
+            .ok_or_else(|| FromHttpRequestError::PathMismatch {
+                received: req.uri().path().to_owned(),
+            })?
+            .into_iter()
+            .map(|arg| percent_decode_str(arg).decode_utf8().map(Cow::into_owned))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Self::try_from_http_request(req, &path_args)
+    }
 }
 
 /// A request type for a Matrix API endpoint, used for sending responses.
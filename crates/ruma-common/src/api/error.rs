@@ -158,6 +158,13 @@ pub enum FromHttpRequestError {
         /// received http method
         received: http::method::Method,
     },
+
+    /// HTTP path doesn't match any of the paths of the endpoint.
+    #[error("http path mismatch: {received} doesn't match any path of the endpoint")]
+    PathMismatch {
+        /// received http path
+        received: String,
+    },
 }
 
 impl<T> From<T> for FromHttpRequestError
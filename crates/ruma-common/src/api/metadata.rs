@@ -284,6 +284,48 @@ impl Metadata {
         Ok(res)
     }
 
+    /// Whether the given request matches the HTTP method and one of the paths of this endpoint.
+    ///
+    /// Returns the path arguments of the request, in the order they appear in the path, if it
+    /// matches. The arguments are not percent-decoded.
+    ///
+    /// This is meant to be used by server implementations that need to route incoming requests
+    /// without duplicating the path definitions of the endpoints.
+    pub fn match_request<'a, B>(&self, request: &'a http::Request<B>) -> Option<Vec<&'a str>> {
+        if request.method() != self.method {
+            return None;
+        }
+
+        self.match_path(request.uri().path())
+    }
+
+    /// Whether the given path matches one of the paths of this endpoint.
+    ///
+    /// Returns the path arguments found in `path`, in the order they appear in the path, if it
+    /// matches. The arguments are not percent-decoded.
+    pub fn match_path<'a>(&self, path: &'a str) -> Option<Vec<&'a str>> {
+        self.history.all_paths().find_map(|path_with_placeholders| {
+            let mut path_args = Vec::new();
+            let mut segments = path.split('/');
+
+            for expected in path_with_placeholders.split('/') {
+                let segment = segments.next()?;
+
+                if Self::extract_endpoint_path_segment_variable(expected).is_some() {
+                    if segment.is_empty() {
+                        return None;
+                    }
+
+                    path_args.push(segment);
+                } else if segment != expected {
+                    return None;
+                }
+            }
+
+            segments.next().is_none().then_some(path_args)
+        })
+    }
+
     /// The list of path parameters in the metadata.
     ///
     /// Used for `#[test]`s generated by the API macros.
@@ -615,17 +657,27 @@ impl VersionHistory {
     /// Returns the *first* version this endpoint was added in.
     ///
     /// Is `None` when this endpoint is unstable/unreleased.
-    pub fn added_in(&self) -> Option<MatrixVersion> {
-        self.stable_paths.iter().find_map(|(v, _)| v.version())
+    pub const fn added_in(&self) -> Option<MatrixVersion> {
+        let mut i = 0;
+
+        while i < self.stable_paths.len() {
+            if let Some(version) = self.stable_paths[i].0.version() {
+                return Some(version);
+            }
+
+            i += 1;
+        }
+
+        None
     }
 
     /// Returns the Matrix version that deprecated this endpoint, if any.
-    pub fn deprecated_in(&self) -> Option<MatrixVersion> {
+    pub const fn deprecated_in(&self) -> Option<MatrixVersion> {
         self.deprecated
     }
 
     /// Returns the Matrix version that removed this endpoint, if any.
-    pub fn removed_in(&self) -> Option<MatrixVersion> {
+    pub const fn removed_in(&self) -> Option<MatrixVersion> {
         self.removed
     }
 
@@ -1629,6 +1681,29 @@ mod tests {
         assert_eq!(random_order_supported.versions, BTreeSet::from([V1_0, V1_1, V1_2]));
     }
 
+    #[test]
+    fn lifecycle_versions_in_const_context() {
+        const HISTORY: VersionHistory = VersionHistory {
+            unstable_paths: &[(None, "/u")],
+            stable_paths: &[
+                (StablePathSelector::Feature("org.boo.stable"), "/f"),
+                (StablePathSelector::Version(V1_1), "/s"),
+            ],
+            deprecated: Some(V1_2),
+            removed: Some(V1_3),
+        };
+        const ADDED: Option<MatrixVersion> = HISTORY.added_in();
+        const DEPRECATED: Option<MatrixVersion> = HISTORY.deprecated_in();
+        const REMOVED: Option<MatrixVersion> = HISTORY.removed_in();
+
+        assert_eq!(ADDED, Some(V1_1));
+        assert_eq!(DEPRECATED, Some(V1_2));
+        assert_eq!(REMOVED, Some(V1_3));
+
+        const UNSTABLE_ADDED: Option<MatrixVersion> = EMPTY.added_in();
+        assert_eq!(UNSTABLE_ADDED, None);
+    }
+
     #[test]
     #[should_panic]
     fn make_endpoint_url_with_path_args_old_syntax() {
@@ -5,9 +5,13 @@ use std::{fmt, mem};
 use serde::Serialize;
 use serde_json::Value as JsonValue;
 
+mod ser;
 mod value;
 
-pub use self::value::{CanonicalJsonObject, CanonicalJsonValue};
+pub use self::{
+    ser::to_canonical_json_string,
+    value::{CanonicalJsonObject, CanonicalJsonValue},
+};
 use crate::{room_version_rules::RedactionRules, serde::Raw};
 
 /// The set of possible errors when serializing to canonical JSON.
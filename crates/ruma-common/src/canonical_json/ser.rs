@@ -0,0 +1,786 @@
+//! A serializer that writes canonical JSON directly, without an intermediate tree of values.
+
+use std::{collections::BTreeMap, fmt};
+
+use js_int::Int;
+use serde::ser::{self, Impossible, Serialize};
+use serde_json::Value as JsonValue;
+
+use super::CanonicalJsonError;
+
+/// Serialize the given value as a canonical JSON string.
+///
+/// This produces the same output as converting the value with [`to_canonical_value`] and
+/// serializing the result, but writes the JSON directly. Only the entries of objects are buffered,
+/// to be able to sort them by key. Raw JSON values, like [`Raw`](crate::serde::Raw), are parsed
+/// to be serialized in canonical form too.
+///
+/// Returns an error if the value contains a floating-point number, an integer outside of the range
+/// of [`js_int::Int`], or a map with keys that can't be represented as strings.
+///
+/// [`to_canonical_value`]: super::to_canonical_value
+pub fn to_canonical_json_string<T>(value: &T) -> Result<String, CanonicalJsonError>
+where
+    T: Serialize + ?Sized,
+{
+    let mut out = Vec::new();
+    value.serialize(Serializer { out: &mut out })?;
+
+    // The serializer only writes valid UTF-8.
+    Ok(String::from_utf8(out).expect("canonical JSON should be valid UTF-8"))
+}
+
+impl ser::Error for CanonicalJsonError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self::SerDe(ser::Error::custom(msg))
+    }
+}
+
+/// Write the given string as a JSON string to the output.
+fn write_str(out: &mut Vec<u8>, value: &str) -> Result<(), CanonicalJsonError> {
+    serde_json::to_writer(out, value).map_err(CanonicalJsonError::SerDe)
+}
+
+/// Write the given integer to the output, if it is in the range of a canonical JSON integer.
+fn write_int(out: &mut Vec<u8>, value: Option<Int>) -> Result<(), CanonicalJsonError> {
+    let value = value.ok_or(CanonicalJsonError::IntConvert)?;
+    serde_json::to_writer(out, &i64::from(value)).map_err(CanonicalJsonError::SerDe)
+}
+
+/// The name of the struct that `serde_json::value::RawValue` serializes as, and of its only field.
+///
+/// The field contains the raw JSON string.
+const RAW_VALUE_TOKEN: &str = "$serde_json::private::RawValue";
+
+fn key_must_be_a_string() -> CanonicalJsonError {
+    ser::Error::custom("key must be a string")
+}
+
+/// A serializer for canonical JSON values.
+struct Serializer<'a> {
+    out: &'a mut Vec<u8>,
+}
+
+impl<'a> ser::Serializer for Serializer<'a> {
+    type Ok = ();
+    type Error = CanonicalJsonError;
+
+    type SerializeSeq = SeqSerializer<'a>;
+    type SerializeTuple = SeqSerializer<'a>;
+    type SerializeTupleStruct = SeqSerializer<'a>;
+    type SerializeTupleVariant = SeqSerializer<'a>;
+    type SerializeMap = ObjectSerializer<'a>;
+    type SerializeStruct = StructSerializer<'a>;
+    type SerializeStructVariant = ObjectSerializer<'a>;
+
+    fn serialize_bool(self, v: bool) -> Result<(), Self::Error> {
+        self.out.extend_from_slice(if v { b"true" } else { b"false" });
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), Self::Error> {
+        write_int(self.out, Some(v.into()))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), Self::Error> {
+        write_int(self.out, Some(v.into()))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), Self::Error> {
+        write_int(self.out, Some(v.into()))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), Self::Error> {
+        write_int(self.out, Int::new(v))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), Self::Error> {
+        write_int(self.out, i64::try_from(v).ok().and_then(Int::new))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), Self::Error> {
+        write_int(self.out, Some(v.into()))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), Self::Error> {
+        write_int(self.out, Some(v.into()))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), Self::Error> {
+        write_int(self.out, Some(v.into()))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), Self::Error> {
+        write_int(self.out, i64::try_from(v).ok().and_then(Int::new))
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), Self::Error> {
+        write_int(self.out, i64::try_from(v).ok().and_then(Int::new))
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<(), Self::Error> {
+        Err(CanonicalJsonError::IntConvert)
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<(), Self::Error> {
+        Err(CanonicalJsonError::IntConvert)
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), Self::Error> {
+        write_str(self.out, v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), Self::Error> {
+        write_str(self.out, v)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), Self::Error> {
+        // Like serde_json, serialize bytes as an array of integers.
+        ser::Serializer::collect_seq(self, v)
+    }
+
+    fn serialize_none(self) -> Result<(), Self::Error> {
+        self.serialize_unit()
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Self::Error> {
+        self.out.extend_from_slice(b"null");
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Self::Error> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), Self::Error> {
+        write_str(self.out, variant)
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        // An object with a single key is always sorted.
+        self.out.push(b'{');
+        write_str(self.out, variant)?;
+        self.out.push(b':');
+        value.serialize(Serializer { out: &mut *self.out })?;
+        self.out.push(b'}');
+        Ok(())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Ok(SeqSerializer::new(self.out, false))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        self.out.push(b'{');
+        write_str(self.out, variant)?;
+        self.out.push(b':');
+        Ok(SeqSerializer::new(self.out, true))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Ok(ObjectSerializer::new(self.out, false))
+    }
+
+    fn serialize_struct(
+        self,
+        name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        if name == RAW_VALUE_TOKEN {
+            Ok(StructSerializer::RawValue(self.out))
+        } else {
+            Ok(StructSerializer::Object(ObjectSerializer::new(self.out, false)))
+        }
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        self.out.push(b'{');
+        write_str(self.out, variant)?;
+        self.out.push(b':');
+        Ok(ObjectSerializer::new(self.out, true))
+    }
+}
+
+/// A serializer for JSON arrays.
+struct SeqSerializer<'a> {
+    out: &'a mut Vec<u8>,
+
+    /// Whether no element was serialized yet.
+    is_empty: bool,
+
+    /// Whether this is a tuple variant, wrapped in an object.
+    is_variant: bool,
+}
+
+impl<'a> SeqSerializer<'a> {
+    fn new(out: &'a mut Vec<u8>, is_variant: bool) -> Self {
+        out.push(b'[');
+        Self { out, is_empty: true, is_variant }
+    }
+
+    fn element<T>(&mut self, value: &T) -> Result<(), CanonicalJsonError>
+    where
+        T: Serialize + ?Sized,
+    {
+        if !self.is_empty {
+            self.out.push(b',');
+        }
+        self.is_empty = false;
+
+        value.serialize(Serializer { out: &mut *self.out })
+    }
+
+    fn finish(self) -> Result<(), CanonicalJsonError> {
+        self.out.push(b']');
+
+        if self.is_variant {
+            self.out.push(b'}');
+        }
+
+        Ok(())
+    }
+}
+
+impl ser::SerializeSeq for SeqSerializer<'_> {
+    type Ok = ();
+    type Error = CanonicalJsonError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for SeqSerializer<'_> {
+    type Ok = ();
+    type Error = CanonicalJsonError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for SeqSerializer<'_> {
+    type Ok = ();
+    type Error = CanonicalJsonError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for SeqSerializer<'_> {
+    type Ok = ();
+    type Error = CanonicalJsonError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        self.finish()
+    }
+}
+
+/// A serializer for JSON objects.
+///
+/// The serialized values are buffered until the end, to be able to write the entries sorted by
+/// key.
+struct ObjectSerializer<'a> {
+    out: &'a mut Vec<u8>,
+
+    /// The serialized entries of the object.
+    entries: BTreeMap<String, Vec<u8>>,
+
+    /// The key of the next entry, when serializing a map.
+    next_key: Option<String>,
+
+    /// Whether this is a struct variant, wrapped in an object.
+    is_variant: bool,
+}
+
+impl<'a> ObjectSerializer<'a> {
+    fn new(out: &'a mut Vec<u8>, is_variant: bool) -> Self {
+        Self { out, entries: BTreeMap::new(), next_key: None, is_variant }
+    }
+
+    fn entry<T>(&mut self, key: String, value: &T) -> Result<(), CanonicalJsonError>
+    where
+        T: Serialize + ?Sized,
+    {
+        let mut buf = Vec::new();
+        value.serialize(Serializer { out: &mut buf })?;
+        self.entries.insert(key, buf);
+
+        Ok(())
+    }
+
+    fn finish(self) -> Result<(), CanonicalJsonError> {
+        self.out.push(b'{');
+
+        for (i, (key, value)) in self.entries.into_iter().enumerate() {
+            if i > 0 {
+                self.out.push(b',');
+            }
+
+            write_str(self.out, &key)?;
+            self.out.push(b':');
+            self.out.extend_from_slice(&value);
+        }
+
+        self.out.push(b'}');
+
+        if self.is_variant {
+            self.out.push(b'}');
+        }
+
+        Ok(())
+    }
+}
+
+impl ser::SerializeMap for ObjectSerializer<'_> {
+    type Ok = ();
+    type Error = CanonicalJsonError;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.next_key = Some(key.serialize(KeySerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        let Some(key) = self.next_key.take() else {
+            return Err(ser::Error::custom("serialize_value called before serialize_key"));
+        };
+        self.entry(key, value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        self.finish()
+    }
+}
+
+/// A serializer for structs.
+enum StructSerializer<'a> {
+    /// A regular struct, serialized as a JSON object.
+    Object(ObjectSerializer<'a>),
+
+    /// A `serde_json::value::RawValue`.
+    ///
+    /// Its JSON is parsed and serialized again, to make sure that it is in canonical form.
+    RawValue(&'a mut Vec<u8>),
+}
+
+impl ser::SerializeStruct for StructSerializer<'_> {
+    type Ok = ();
+    type Error = CanonicalJsonError;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        match self {
+            Self::Object(serializer) => serializer.entry(key.to_owned(), value),
+            Self::RawValue(out) => {
+                if key != RAW_VALUE_TOKEN {
+                    return Err(ser::Error::custom("unexpected field in raw JSON value"));
+                }
+
+                // The raw JSON is serialized as a string, which the key serializer accepts.
+                let json = value.serialize(KeySerializer)?;
+                let value =
+                    serde_json::from_str::<JsonValue>(&json).map_err(CanonicalJsonError::SerDe)?;
+                value.serialize(Serializer { out: &mut **out })
+            }
+        }
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        match self {
+            Self::Object(serializer) => serializer.finish(),
+            Self::RawValue(_) => Ok(()),
+        }
+    }
+}
+
+impl ser::SerializeStructVariant for ObjectSerializer<'_> {
+    type Ok = ();
+    type Error = CanonicalJsonError;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.entry(key.to_owned(), value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        self.finish()
+    }
+}
+
+/// A serializer for the keys of JSON objects.
+///
+/// Like serde_json, it accepts strings and values that can be converted to strings without
+/// ambiguity.
+struct KeySerializer;
+
+impl ser::Serializer for KeySerializer {
+    type Ok = String;
+    type Error = CanonicalJsonError;
+
+    type SerializeSeq = Impossible<String, CanonicalJsonError>;
+    type SerializeTuple = Impossible<String, CanonicalJsonError>;
+    type SerializeTupleStruct = Impossible<String, CanonicalJsonError>;
+    type SerializeTupleVariant = Impossible<String, CanonicalJsonError>;
+    type SerializeMap = Impossible<String, CanonicalJsonError>;
+    type SerializeStruct = Impossible<String, CanonicalJsonError>;
+    type SerializeStructVariant = Impossible<String, CanonicalJsonError>;
+
+    fn serialize_bool(self, v: bool) -> Result<String, Self::Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<String, Self::Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<String, Self::Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<String, Self::Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<String, Self::Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<String, Self::Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<String, Self::Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<String, Self::Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<String, Self::Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<String, Self::Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<String, Self::Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_char(self, v: char) -> Result<String, Self::Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<String, Self::Error> {
+        Ok(v.to_owned())
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<String, Self::Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_none(self) -> Result<String, Self::Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_some<T>(self, _value: &T) -> Result<String, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_unit(self) -> Result<String, Self::Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<String, Self::Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<String, Self::Error> {
+        Ok(variant.to_owned())
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<String, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<String, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(key_must_be_a_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use assert_matches2::assert_matches;
+    use serde::Serialize;
+    use serde_json::{json, Value as JsonValue};
+
+    use super::to_canonical_json_string;
+    use crate::{
+        canonical_json::{to_canonical_value, CanonicalJsonError},
+        serde::Raw,
+    };
+
+    #[derive(Serialize)]
+    struct Event {
+        sender: &'static str,
+        origin_server_ts: u64,
+        content: Content,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        state_key: Option<&'static str>,
+        prev_events: Vec<&'static str>,
+        kind: Kind,
+    }
+
+    #[derive(Serialize)]
+    struct Content {
+        zeta: &'static str,
+        alpha: &'static str,
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "snake_case")]
+    enum Kind {
+        Message { body: &'static str, formatted: bool },
+    }
+
+    #[test]
+    fn same_as_canonical_value() {
+        let event = Event {
+            sender: "@alice:example.org",
+            origin_server_ts: 1_234_567,
+            content: Content { zeta: "\"quoted\"\n", alpha: "é" },
+            state_key: None,
+            prev_events: vec!["$a", "$b"],
+            kind: Kind::Message { body: "hello", formatted: false },
+        };
+
+        let canonical = to_canonical_json_string(&event).unwrap();
+        assert_eq!(canonical, to_canonical_value(&event).unwrap().to_string());
+        assert_eq!(
+            canonical,
+            r#"{"content":{"alpha":"é","zeta":"\"quoted\"\n"},"kind":{"message":{"body":"hello","formatted":false}},"origin_server_ts":1234567,"prev_events":["$a","$b"],"sender":"@alice:example.org"}"#
+        );
+    }
+
+    #[test]
+    fn raw_value() {
+        #[derive(Serialize)]
+        struct EventWithRaw {
+            sender: &'static str,
+            content: Raw<JsonValue>,
+        }
+
+        let event = EventWithRaw {
+            sender: "@alice:example.org",
+            content: Raw::from_json_string(
+                r#"{ "zeta": [1, { "b": 2, "a": null }], "alpha": "é" }"#.to_owned(),
+            )
+            .unwrap(),
+        };
+
+        let canonical = to_canonical_json_string(&event).unwrap();
+        assert_eq!(canonical, to_canonical_value(&event).unwrap().to_string());
+        assert_eq!(
+            canonical,
+            r#"{"content":{"alpha":"é","zeta":[1,{"a":null,"b":2}]},"sender":"@alice:example.org"}"#
+        );
+
+        let raw = Raw::<JsonValue>::from_json_string(r#"{ "a": 1.5 }"#.to_owned()).unwrap();
+        assert_matches!(to_canonical_json_string(&raw), Err(CanonicalJsonError::IntConvert));
+    }
+
+    #[test]
+    fn json_value() {
+        let value = json!({ "b": [1, { "d": null, "c": true }], "a": "" });
+        assert_eq!(
+            to_canonical_json_string(&value).unwrap(),
+            r#"{"a":"","b":[1,{"c":true,"d":null}]}"#
+        );
+    }
+
+    #[test]
+    fn reject_floats_and_big_integers() {
+        assert_matches!(
+            to_canonical_json_string(&json!({ "a": 1.5 })),
+            Err(CanonicalJsonError::IntConvert)
+        );
+        assert_matches!(to_canonical_json_string(&u64::MAX), Err(CanonicalJsonError::IntConvert));
+    }
+
+    #[test]
+    fn integer_keys() {
+        let map = BTreeMap::from([(10, "ten"), (2, "two")]);
+        assert_eq!(to_canonical_json_string(&map).unwrap(), r#"{"10":"ten","2":"two"}"#);
+
+        let map = BTreeMap::from([((1, 2), "pair")]);
+        assert_matches!(to_canonical_json_string(&map), Err(CanonicalJsonError::SerDe(_)));
+    }
+}
@@ -0,0 +1,350 @@
+//! Matching of strings against glob patterns.
+//!
+//! Glob patterns are used in several places of the Matrix specification, like the conditions of
+//! push rules, server ACLs or moderation policy lists. In a glob, `*` matches zero or more
+//! characters and `?` matches exactly one character.
+
+use std::str::FromStr;
+
+use regex::bytes::Regex;
+use wildmatch::WildMatch;
+
+/// Whether `value` matches the glob `pattern`.
+///
+/// The whole value must match the pattern.
+///
+/// The match is case sensitive.
+///
+/// # Example
+///
+/// ```
+/// use ruma_common::glob;
+///
+/// assert!(glob::matches("*.matrix.org", "ruma.matrix.org"));
+/// assert!(!glob::matches("*.matrix.org", "matrix.org"));
+/// assert!(glob::matches("matrix.???", "matrix.org"));
+/// ```
+pub fn matches(pattern: &str, value: &str) -> bool {
+    WildMatch::new(pattern).matches(value)
+}
+
+/// Whether `value` matches the glob `pattern`, ignoring case.
+///
+/// The whole value must match the pattern.
+///
+/// This is the behavior of the `event_match` condition of push rules for most keys.
+pub fn matches_case_insensitive(pattern: &str, value: &str) -> bool {
+    value.matches_pattern(pattern, false)
+}
+
+/// Whether `value` contains a sequence of words matching the glob `pattern`.
+///
+/// A word boundary is defined as the start or end of the value, or any character not in the
+/// sets `[A-Z]`, `[a-z]`, `[0-9]` or `_`. The pattern must match a part of the value that is
+/// separated from other words by such a boundary.
+///
+/// The match is case insensitive.
+///
+/// This is the behavior of the `event_match` condition of push rules for the `content.body` key,
+/// and of the `contains_display_name` condition.
+///
+/// # Example
+///
+/// ```
+/// use ruma_common::glob;
+///
+/// assert!(glob::matches_words("ex*ple", "An example event."));
+/// assert!(glob::matches_words("lunch", "Lunch plans?"));
+/// assert!(!glob::matches_words("lunch", "Lunchtime!"));
+/// ```
+pub fn matches_words(pattern: &str, value: &str) -> bool {
+    value.matches_pattern(pattern, true)
+}
+
+/// Additional functions for character matching.
+trait CharExt {
+    /// Whether or not this char can be part of a word.
+    fn is_word_char(&self) -> bool;
+}
+
+impl CharExt for char {
+    fn is_word_char(&self) -> bool {
+        self.is_ascii_alphanumeric() || *self == '_'
+    }
+}
+
+/// Additional functions for string matching.
+pub(crate) trait StrExt {
+    /// Get the length of the char at `index`. The byte index must correspond to
+    /// the start of a char boundary.
+    fn char_len(&self, index: usize) -> usize;
+
+    /// Get the char at `index`. The byte index must correspond to the start of
+    /// a char boundary.
+    fn char_at(&self, index: usize) -> char;
+
+    /// Get the index of the char that is before the char at `index`. The byte index
+    /// must correspond to a char boundary.
+    ///
+    /// Returns `None` if there's no previous char. Otherwise, returns the char.
+    fn find_prev_char(&self, index: usize) -> Option<char>;
+
+    /// Matches this string against `pattern`.
+    ///
+    /// The pattern can be a glob with wildcards `*` and `?`.
+    ///
+    /// The match is case insensitive.
+    ///
+    /// If `match_words` is `true`, checks that the pattern is separated from other words.
+    fn matches_pattern(&self, pattern: &str, match_words: bool) -> bool;
+
+    /// Matches this string against `pattern`, with word boundaries.
+    ///
+    /// The pattern can be a glob with wildcards `*` and `?`.
+    ///
+    /// A word boundary is defined as the start or end of the value, or any character not in the
+    /// sets `[A-Z]`, `[a-z]`, `[0-9]` or `_`.
+    ///
+    /// The match is case sensitive.
+    fn matches_word(&self, pattern: &str) -> bool;
+
+    /// Translate the wildcards in `self` to a regex syntax.
+    ///
+    /// `self` must only contain wildcards.
+    fn wildcards_to_regex(&self) -> String;
+}
+
+impl StrExt for str {
+    fn char_len(&self, index: usize) -> usize {
+        let mut len = 1;
+        while !self.is_char_boundary(index + len) {
+            len += 1;
+        }
+        len
+    }
+
+    fn char_at(&self, index: usize) -> char {
+        let end = index + self.char_len(index);
+        let char_str = &self[index..end];
+        char::from_str(char_str)
+            .unwrap_or_else(|_| panic!("Could not convert str '{char_str}' to char"))
+    }
+
+    fn find_prev_char(&self, index: usize) -> Option<char> {
+        if index == 0 {
+            return None;
+        }
+
+        let mut pos = index - 1;
+        while !self.is_char_boundary(pos) {
+            pos -= 1;
+        }
+        Some(self.char_at(pos))
+    }
+
+    fn matches_pattern(&self, pattern: &str, match_words: bool) -> bool {
+        let value = &self.to_lowercase();
+        let pattern = &pattern.to_lowercase();
+
+        if match_words {
+            value.matches_word(pattern)
+        } else {
+            matches(pattern, value)
+        }
+    }
+
+    fn matches_word(&self, pattern: &str) -> bool {
+        if self == pattern {
+            return true;
+        }
+        if pattern.is_empty() {
+            return false;
+        }
+
+        let has_wildcards = pattern.contains(['?', '*']);
+
+        if has_wildcards {
+            let mut chunks: Vec<String> = vec![];
+            let mut prev_wildcard = false;
+            let mut chunk_start = 0;
+
+            for (i, c) in pattern.char_indices() {
+                if matches!(c, '?' | '*') && !prev_wildcard {
+                    if i != 0 {
+                        chunks.push(regex::escape(&pattern[chunk_start..i]));
+                        chunk_start = i;
+                    }
+
+                    prev_wildcard = true;
+                } else if prev_wildcard {
+                    let chunk = &pattern[chunk_start..i];
+                    chunks.push(chunk.wildcards_to_regex());
+
+                    chunk_start = i;
+                    prev_wildcard = false;
+                }
+            }
+
+            let len = pattern.len();
+            if !prev_wildcard {
+                chunks.push(regex::escape(&pattern[chunk_start..len]));
+            } else if prev_wildcard {
+                let chunk = &pattern[chunk_start..len];
+                chunks.push(chunk.wildcards_to_regex());
+            }
+
+            // The word characters in ASCII compatible mode (with the `-u` flag) match the
+            // definition in the spec: any character not in the set `[A-Za-z0-9_]`.
+            let regex = format!(r"(?-u:^|\W|\b){}(?-u:\b|\W|$)", chunks.concat());
+            let re = Regex::new(&regex).expect("regex construction should succeed");
+            re.is_match(self.as_bytes())
+        } else {
+            match self.find(pattern) {
+                Some(start) => {
+                    let end = start + pattern.len();
+
+                    // Look if the match has word boundaries.
+                    let word_boundary_start = !self.char_at(start).is_word_char()
+                        || !self.find_prev_char(start).is_some_and(|c| c.is_word_char());
+
+                    if word_boundary_start {
+                        let word_boundary_end = end == self.len()
+                            || !self.find_prev_char(end).unwrap().is_word_char()
+                            || !self.char_at(end).is_word_char();
+
+                        if word_boundary_end {
+                            return true;
+                        }
+                    }
+
+                    // Find next word.
+                    let non_word_str = &self[start..];
+                    let Some(non_word) = non_word_str.find(|c: char| !c.is_word_char()) else {
+                        return false;
+                    };
+
+                    let word_str = &non_word_str[non_word..];
+                    let Some(word) = word_str.find(|c: char| c.is_word_char()) else {
+                        return false;
+                    };
+
+                    word_str[word..].matches_word(pattern)
+                }
+                None => false,
+            }
+        }
+    }
+
+    fn wildcards_to_regex(&self) -> String {
+        // Simplify pattern to avoid performance issues:
+        // - The glob `?**?**?` is equivalent to the glob `???*`
+        // - The glob `???*` is equivalent to the regex `.{3,}`
+        let question_marks = self.matches('?').count();
+
+        if self.contains('*') {
+            format!(".{{{question_marks},}}")
+        } else {
+            format!(".{{{question_marks}}}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{matches, matches_case_insensitive, matches_words, StrExt};
+
+    #[test]
+    fn words_match() {
+        assert!("foo bar".matches_word("foo"));
+        assert!(!"Foo bar".matches_word("foo"));
+        assert!(!"foobar".matches_word("foo"));
+        assert!("foobar foo".matches_word("foo"));
+        assert!(!"foobar foobar".matches_word("foo"));
+        assert!(!"foobar bar".matches_word("bar bar"));
+        assert!("foobar bar bar".matches_word("bar bar"));
+        assert!(!"foobar bar barfoo".matches_word("bar bar"));
+        assert!("conduit ⚡️".matches_word("conduit ⚡️"));
+        assert!("conduit ⚡️".matches_word("conduit"));
+        assert!("conduit ⚡️".matches_word("⚡️"));
+        assert!("conduit⚡️".matches_word("conduit"));
+        assert!("conduit⚡️".matches_word("⚡️"));
+        assert!("⚡️conduit".matches_word("conduit"));
+        assert!("⚡️conduit".matches_word("⚡️"));
+        assert!("Ruma Dev👩‍💻".matches_word("Dev"));
+        assert!("Ruma Dev👩‍💻".matches_word("👩‍💻"));
+        assert!("Ruma Dev👩‍💻".matches_word("Dev👩‍💻"));
+
+        // Regex syntax is escaped
+        assert!(!"matrix".matches_word(r"\w*"));
+        assert!(r"\w".matches_word(r"\w*"));
+        assert!(!"matrix".matches_word("[a-z]*"));
+        assert!("[a-z] and [0-9]".matches_word("[a-z]*"));
+        assert!(!"m".matches_word("[[:alpha:]]?"));
+        assert!("[[:alpha:]]!".matches_word("[[:alpha:]]?"));
+
+        // From the spec: <https://spec.matrix.org/v1.15/client-server-api/#conditions-1>
+        assert!("An example event.".matches_word("ex*ple"));
+        assert!("exple".matches_word("ex*ple"));
+        assert!("An exciting triple-whammy".matches_word("ex*ple"));
+    }
+
+    #[test]
+    fn patterns_match() {
+        // Word matching without glob
+        assert!("foo bar".matches_pattern("foo", true));
+        assert!("Foo bar".matches_pattern("foo", true));
+        assert!(!"foobar".matches_pattern("foo", true));
+        assert!("".matches_pattern("", true));
+        assert!(!"foo".matches_pattern("", true));
+        assert!("foo bar".matches_pattern("foo bar", true));
+        assert!(" foo bar ".matches_pattern("foo bar", true));
+        assert!("baz foo bar baz".matches_pattern("foo bar", true));
+        assert!("foo baré".matches_pattern("foo bar", true));
+        assert!(!"bar foo".matches_pattern("foo bar", true));
+        assert!("foo bar".matches_pattern("foo ", true));
+        assert!("foo ".matches_pattern("foo ", true));
+        assert!("foo  ".matches_pattern("foo ", true));
+        assert!(" foo  ".matches_pattern("foo ", true));
+
+        // Word matching with glob
+        assert!("foo bar".matches_pattern("foo*", true));
+        assert!("foo bar".matches_pattern("foo b?r", true));
+        assert!(" foo bar ".matches_pattern("foo b?r", true));
+        assert!("baz foo bar baz".matches_pattern("foo b?r", true));
+        assert!("foo baré".matches_pattern("foo b?r", true));
+        assert!(!"bar foo".matches_pattern("foo b?r", true));
+        assert!("foo bar".matches_pattern("f*o ", true));
+        assert!("foo ".matches_pattern("f*o ", true));
+        assert!("foo  ".matches_pattern("f*o ", true));
+        assert!(" foo  ".matches_pattern("f*o ", true));
+
+        // Glob matching
+        assert!(!"foo bar".matches_pattern("foo", false));
+        assert!("foo".matches_pattern("foo", false));
+        assert!("foo".matches_pattern("foo*", false));
+        assert!("foobar".matches_pattern("foo*", false));
+        assert!("foo bar".matches_pattern("foo*", false));
+        assert!(!"foo".matches_pattern("foo?", false));
+        assert!("fooo".matches_pattern("foo?", false));
+        assert!("FOO".matches_pattern("foo", false));
+        assert!("".matches_pattern("", false));
+        assert!("".matches_pattern("*", false));
+        assert!(!"foo".matches_pattern("", false));
+
+        // From the spec: <https://spec.matrix.org/v1.15/client-server-api/#conditions-1>
+        assert!("Lunch plans".matches_pattern("lunc?*", false));
+        assert!("LUNCH".matches_pattern("lunc?*", false));
+        assert!(!" lunch".matches_pattern("lunc?*", false));
+        assert!(!"lunc".matches_pattern("lunc?*", false));
+    }
+
+    #[test]
+    fn public_functions_match() {
+        assert!(matches("*.example.org", "foo.example.org"));
+        assert!(!matches("*.example.org", "FOO.EXAMPLE.ORG"));
+        assert!(matches_case_insensitive("*.example.org", "FOO.EXAMPLE.ORG"));
+        assert!(!matches_case_insensitive("*.example.org", "example.org"));
+        assert!(matches_words("foo b?r", "Baz FOO BAR baz"));
+        assert!(!matches_words("foo", "foobar"));
+    }
+}
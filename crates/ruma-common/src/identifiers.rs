@@ -122,8 +122,8 @@ macro_rules! owned_device_id {
 #[doc(hidden)]
 pub mod __private_macros {
     pub use ruma_macros::{
-        base64_public_key, event_id, mxc_uri, room_alias_id, room_id, room_version_id, server_name,
-        server_signing_key_version, user_id,
+        base64_public_key, device_key_id, event_id, mxc_uri, room_alias_id, room_id,
+        room_version_id, server_name, server_signing_key_id, server_signing_key_version, user_id,
     };
 }
 
@@ -199,6 +199,38 @@ macro_rules! owned_server_signing_key_version {
     };
 }
 
+/// Compile-time checked [`ServerSigningKeyId`] construction.
+#[macro_export]
+macro_rules! server_signing_key_id {
+    ($s:literal) => {
+        $crate::__private_macros::server_signing_key_id!($crate, $s)
+    };
+}
+
+/// Compile-time checked [`OwnedServerSigningKeyId`] construction.
+#[macro_export]
+macro_rules! owned_server_signing_key_id {
+    ($s:literal) => {
+        $crate::server_signing_key_id!($s).to_owned()
+    };
+}
+
+/// Compile-time checked [`DeviceKeyId`] construction.
+#[macro_export]
+macro_rules! device_key_id {
+    ($s:literal) => {
+        $crate::__private_macros::device_key_id!($crate, $s)
+    };
+}
+
+/// Compile-time checked [`OwnedDeviceKeyId`] construction.
+#[macro_export]
+macro_rules! owned_device_key_id {
+    ($s:literal) => {
+        $crate::device_key_id!($s).to_owned()
+    };
+}
+
 /// Compile-time checked [`ServerName`] construction.
 #[macro_export]
 macro_rules! server_name {
@@ -4,6 +4,7 @@
 
 use std::time::Duration;
 
+use js_int::UInt;
 use ruma_macros::{OrdAsRefStr, PartialEqAsRefStr, PartialOrdAsRefStr};
 
 use crate::{serde::StringEnum, PrivOwnedStr};
@@ -34,3 +35,60 @@ pub fn default_download_timeout() -> Duration {
 pub fn is_default_download_timeout(timeout: &Duration) -> bool {
     timeout.as_secs() == 20
 }
+
+/// Get the smallest thumbnail size recommended by the [spec] for the given method that is at least
+/// as large as the given dimensions.
+///
+/// Servers should pre-generate thumbnails with these sizes, so requesting them is more likely to
+/// hit a cache.
+///
+/// Returns `None` if the given dimensions are larger than all the recommended sizes for the
+/// method, or if the method is unknown.
+///
+/// [spec]: https://spec.matrix.org/latest/client-server-api/#thumbnails
+pub fn recommended_thumbnail_size(
+    method: &Method,
+    width: UInt,
+    height: UInt,
+) -> Option<(UInt, UInt)> {
+    let sizes: &[(u32, u32)] = match method {
+        Method::Crop => &[(32, 32), (96, 96)],
+        Method::Scale => &[(320, 240), (640, 480), (800, 600)],
+        Method::_Custom(_) => return None,
+    };
+
+    sizes
+        .iter()
+        .map(|&(w, h)| (UInt::from(w), UInt::from(h)))
+        .find(|&(w, h)| w >= width && h >= height)
+}
+
+#[cfg(test)]
+mod tests {
+    use js_int::uint;
+
+    use super::{recommended_thumbnail_size, Method};
+
+    #[test]
+    fn recommended_thumbnail_sizes() {
+        assert_eq!(
+            recommended_thumbnail_size(&Method::Crop, uint!(24), uint!(24)),
+            Some((uint!(32), uint!(32)))
+        );
+        assert_eq!(
+            recommended_thumbnail_size(&Method::Crop, uint!(40), uint!(20)),
+            Some((uint!(96), uint!(96)))
+        );
+        assert_eq!(recommended_thumbnail_size(&Method::Crop, uint!(100), uint!(100)), None);
+
+        assert_eq!(
+            recommended_thumbnail_size(&Method::Scale, uint!(32), uint!(32)),
+            Some((uint!(320), uint!(240)))
+        );
+        assert_eq!(
+            recommended_thumbnail_size(&Method::Scale, uint!(500), uint!(500)),
+            Some((uint!(800), uint!(600)))
+        );
+        assert_eq!(recommended_thumbnail_size(&Method::Scale, uint!(1000), uint!(600)), None);
+    }
+}
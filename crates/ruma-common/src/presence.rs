@@ -2,7 +2,10 @@
 //!
 //! [presence]: https://spec.matrix.org/latest/client-server-api/#presence
 
-use crate::{serde::StringEnum, PrivOwnedStr};
+use js_int::UInt;
+use web_time::Duration;
+
+use crate::{serde::StringEnum, MilliSecondsSinceUnixEpoch, PrivOwnedStr};
 
 /// A description of a user's connectivity and availability for chat.
 #[doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/doc/string_enum.md"))]
@@ -29,3 +32,73 @@ impl Default for &'_ PresenceState {
         &PresenceState::Online
     }
 }
+
+/// When a user was last active, as it can be presented to other users.
+///
+/// It can be computed from the `currently_active` and `last_active_ago` fields of the presence
+/// of a user with [`LastActive::new()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(not(ruma_unstable_exhaustive_types), non_exhaustive)]
+pub enum LastActive {
+    /// The user is currently active.
+    Now,
+
+    /// The user was last active at the given time.
+    At(MilliSecondsSinceUnixEpoch),
+
+    /// It is not known when the user was last active.
+    Unknown,
+}
+
+impl LastActive {
+    /// Computes when a user was last active from the `currently_active` and `last_active_ago`
+    /// fields of their presence.
+    ///
+    /// Since `last_active_ago` is relative to the moment the presence was sent by the server,
+    /// `received_at` should be the time when the presence was received.
+    pub fn new(
+        currently_active: Option<bool>,
+        last_active_ago: Option<Duration>,
+        received_at: MilliSecondsSinceUnixEpoch,
+    ) -> Self {
+        if currently_active == Some(true) {
+            return Self::Now;
+        }
+
+        match last_active_ago {
+            Some(ago) => {
+                let ago = UInt::new_saturating(ago.as_millis().try_into().unwrap_or(u64::MAX));
+                Self::At(MilliSecondsSinceUnixEpoch(received_at.0.saturating_sub(ago)))
+            }
+            None => Self::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use js_int::uint;
+    use web_time::Duration;
+
+    use super::LastActive;
+    use crate::MilliSecondsSinceUnixEpoch;
+
+    #[test]
+    fn last_active() {
+        let received_at = MilliSecondsSinceUnixEpoch(uint!(10_000));
+
+        assert_eq!(
+            LastActive::new(Some(true), Some(Duration::from_secs(2)), received_at),
+            LastActive::Now
+        );
+        assert_eq!(
+            LastActive::new(Some(false), Some(Duration::from_secs(2)), received_at),
+            LastActive::At(MilliSecondsSinceUnixEpoch(uint!(8_000)))
+        );
+        assert_eq!(
+            LastActive::new(None, Some(Duration::from_secs(20)), received_at),
+            LastActive::At(MilliSecondsSinceUnixEpoch(uint!(0)))
+        );
+        assert_eq!(LastActive::new(None, None, received_at), LastActive::Unknown);
+    }
+}
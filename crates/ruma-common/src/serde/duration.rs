@@ -1,6 +1,62 @@
 //! De-/serialization functions for `std::time::Duration` objects
 
+use js_int::UInt;
+use serde::de::{Deserialize, Deserializer};
+
 pub mod ms;
 pub mod opt_ms;
 pub mod opt_secs;
 pub mod secs;
+
+/// Convert the given integer to a `UInt`, saturating at `UInt::MAX`.
+fn saturating_uint(value: impl TryInto<UInt>) -> UInt {
+    value.try_into().unwrap_or(UInt::MAX)
+}
+
+/// A `UInt` that can also be deserialized from a string containing an integer, with the
+/// `compat-stringified-duration` cargo feature.
+struct DurationUInt(UInt);
+
+impl<'de> Deserialize<'de> for DurationUInt {
+    #[cfg(not(feature = "compat-stringified-duration"))]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        UInt::deserialize(deserializer).map(Self)
+    }
+
+    #[cfg(feature = "compat-stringified-duration")]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use std::fmt;
+
+        use serde::de::{Error, Visitor};
+
+        struct DurationUIntVisitor;
+
+        impl Visitor<'_> for DurationUIntVisitor {
+            type Value = UInt;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a non-negative integer or a string containing one")
+            }
+
+            fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
+                UInt::try_from(v).map_err(E::custom)
+            }
+
+            fn visit_i64<E: Error>(self, v: i64) -> Result<Self::Value, E> {
+                u64::try_from(v).map_err(E::custom).and_then(|v| self.visit_u64(v))
+            }
+
+            fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+                v.trim().parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(DurationUIntVisitor).map(Self)
+    }
+}
@@ -2,27 +2,27 @@
 //! milliseconds.
 //!
 //! Delegates to `js_int::UInt` to ensure integer size is within bounds.
+//!
+//! With the `compat-stringified-duration` cargo feature, strings containing an integer are also
+//! accepted during deserialization.
 
 use std::time::Duration;
 
-use js_int::UInt;
 use serde::{
     de::{Deserialize, Deserializer},
-    ser::{Error, Serialize, Serializer},
+    ser::{Serialize, Serializer},
 };
 
+use super::DurationUInt;
+
 /// Serializes a Duration to an integer representing seconds.
 ///
-/// Will fail if integer is greater than the maximum integer that can be
-/// unambiguously represented by an f64.
+/// Saturates at the maximum integer that can be unambiguously represented by an f64.
 pub fn serialize<S>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
-    match UInt::try_from(duration.as_millis()) {
-        Ok(uint) => uint.serialize(serializer),
-        Err(err) => Err(S::Error::custom(err)),
-    }
+    super::saturating_uint(duration.as_millis()).serialize(serializer)
 }
 
 /// Deserializes an integer representing seconds into a Duration.
@@ -33,7 +33,7 @@ pub fn deserialize<'de, D>(deserializer: D) -> Result<Duration, D::Error>
 where
     D: Deserializer<'de>,
 {
-    UInt::deserialize(deserializer).map(|ms| Duration::from_millis(ms.into()))
+    DurationUInt::deserialize(deserializer).map(|DurationUInt(ms)| Duration::from_millis(ms.into()))
 }
 
 #[cfg(test)]
@@ -64,4 +64,24 @@ mod tests {
         let test = DurationTest { timeout: Duration::from_millis(7000) };
         assert_eq!(serde_json::to_value(test).unwrap(), json!({ "timeout": 7000 }));
     }
+
+    #[test]
+    fn serialize_saturating() {
+        let test = DurationTest { timeout: Duration::MAX };
+        assert_eq!(
+            serde_json::to_value(test).unwrap(),
+            json!({ "timeout": 9_007_199_254_740_991_u64 })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "compat-stringified-duration")]
+    fn deserialize_stringified() {
+        let json = json!({ "timeout": "3000" });
+
+        assert_eq!(
+            serde_json::from_value::<DurationTest>(json).unwrap(),
+            DurationTest { timeout: Duration::from_secs(3) },
+        );
+    }
 }
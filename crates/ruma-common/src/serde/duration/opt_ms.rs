@@ -2,28 +2,28 @@
 //! milliseconds.
 //!
 //! Delegates to `js_int::UInt` to ensure integer size is within bounds.
+//!
+//! With the `compat-stringified-duration` cargo feature, strings containing an integer are also
+//! accepted during deserialization.
 
 use std::time::Duration;
 
-use js_int::UInt;
 use serde::{
     de::{Deserialize, Deserializer},
-    ser::{Error, Serialize, Serializer},
+    ser::{Serialize, Serializer},
 };
 
+use super::DurationUInt;
+
 /// Serialize an `Option<Duration>`.
 ///
-/// Will fail if integer is greater than the maximum integer that can be
-/// unambiguously represented by an f64.
+/// Saturates at the maximum integer that can be unambiguously represented by an f64.
 pub fn serialize<S>(opt_duration: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
     match opt_duration {
-        Some(duration) => match UInt::try_from(duration.as_millis()) {
-            Ok(uint) => uint.serialize(serializer),
-            Err(err) => Err(S::Error::custom(err)),
-        },
+        Some(duration) => super::saturating_uint(duration.as_millis()).serialize(serializer),
         None => serializer.serialize_none(),
     }
 }
@@ -36,8 +36,8 @@ pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error
 where
     D: Deserializer<'de>,
 {
-    Ok(Option::<UInt>::deserialize(deserializer)?
-        .map(|millis| Duration::from_millis(millis.into())))
+    Ok(Option::<DurationUInt>::deserialize(deserializer)?
+        .map(|DurationUInt(millis)| Duration::from_millis(millis.into())))
 }
 
 #[cfg(test)]
@@ -2,27 +2,27 @@
 //! milliseconds.
 //!
 //! Delegates to `js_int::UInt` to ensure integer size is within bounds.
+//!
+//! With the `compat-stringified-duration` cargo feature, strings containing an integer are also
+//! accepted during deserialization.
 
 use std::time::Duration;
 
-use js_int::UInt;
 use serde::{
     de::{Deserialize, Deserializer},
-    ser::{Error, Serialize, Serializer},
+    ser::{Serialize, Serializer},
 };
 
+use super::DurationUInt;
+
 /// Serializes a Duration to an integer representing seconds.
 ///
-/// Will fail if integer is greater than the maximum integer that can be
-/// unambiguously represented by an f64.
+/// Saturates at the maximum integer that can be unambiguously represented by an f64.
 pub fn serialize<S>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
-    match UInt::try_from(duration.as_secs()) {
-        Ok(uint) => uint.serialize(serializer),
-        Err(err) => Err(S::Error::custom(err)),
-    }
+    super::saturating_uint(duration.as_secs()).serialize(serializer)
 }
 
 /// Deserializes an integer representing seconds into a Duration.
@@ -33,7 +33,8 @@ pub fn deserialize<'de, D>(deserializer: D) -> Result<Duration, D::Error>
 where
     D: Deserializer<'de>,
 {
-    UInt::deserialize(deserializer).map(|secs| Duration::from_secs(secs.into()))
+    DurationUInt::deserialize(deserializer)
+        .map(|DurationUInt(secs)| Duration::from_secs(secs.into()))
 }
 
 #[cfg(test)]
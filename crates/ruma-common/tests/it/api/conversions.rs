@@ -1,10 +1,12 @@
 #![allow(clippy::exhaustive_structs)]
 
+use assert_matches2::assert_matches;
 use http::header::CONTENT_TYPE;
 use ruma_common::{
     api::{
-        request, response, IncomingRequest as _, MatrixVersion, Metadata, OutgoingRequest as _,
-        OutgoingRequestAppserviceExt, SendAccessToken, SupportedVersions,
+        error::FromHttpRequestError, request, response, IncomingRequest as _, MatrixVersion,
+        Metadata, OutgoingRequest as _, OutgoingRequestAppserviceExt, SendAccessToken,
+        SupportedVersions,
     },
     metadata, owned_user_id, user_id, OwnedUserId,
 };
@@ -82,6 +84,61 @@ fn request_serde() {
     assert_eq!(req.user, req2.user);
 }
 
+#[test]
+fn request_serde_with_path_matching() {
+    let req = Request {
+        hello: "hi".to_owned(),
+        world: "test".to_owned(),
+        q1: "query_param_special_chars %/&@!".to_owned(),
+        q2: 55,
+        bar: "bar/Val".to_owned(),
+        user: owned_user_id!("@bazme:ruma.io"),
+    };
+    let supported =
+        SupportedVersions { versions: [MatrixVersion::V1_1].into(), features: Default::default() };
+
+    let http_req = req
+        .clone()
+        .try_into_http_request::<Vec<u8>>(
+            "https://homeserver.tld",
+            SendAccessToken::None,
+            &supported,
+        )
+        .unwrap();
+    assert_eq!(METADATA.match_request(&http_req), Some(vec!["bar%2FVal", "@bazme:ruma.io"]));
+
+    let req2 = Request::try_from_http_request_with_path(http_req).unwrap();
+
+    assert_eq!(req.hello, req2.hello);
+    assert_eq!(req.world, req2.world);
+    assert_eq!(req.q1, req2.q1);
+    assert_eq!(req.q2, req2.q2);
+    assert_eq!(req.bar, req2.bar);
+    assert_eq!(req.user, req2.user);
+}
+
+#[test]
+fn request_with_path_mismatch() {
+    let http_req = http::Request::post("https://homeserver.tld/_matrix/foo/barVal")
+        .body(b"{}".as_slice())
+        .unwrap();
+    assert_eq!(METADATA.match_request(&http_req), None);
+    assert_matches!(
+        Request::try_from_http_request_with_path(http_req),
+        Err(FromHttpRequestError::PathMismatch { .. })
+    );
+
+    let http_req =
+        http::Request::get("https://homeserver.tld/_matrix/foo/barVal/%40bazme%3Aruma.io")
+            .body(b"{}".as_slice())
+            .unwrap();
+    assert_eq!(METADATA.match_request(&http_req), None);
+    assert_matches!(
+        Request::try_from_http_request_with_path(http_req),
+        Err(FromHttpRequestError::MethodMismatch { .. })
+    );
+}
+
 #[test]
 fn invalid_uri_should_not_panic() {
     let req = Request {
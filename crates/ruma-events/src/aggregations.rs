@@ -0,0 +1,143 @@
+//! Helpers to aggregate [event relationships] on the client side.
+//!
+//! Servers only bundle aggregations with the events they return, so clients need to update them
+//! with the related events they receive afterwards.
+//!
+//! [event relationships]: https://spec.matrix.org/latest/client-server-api/#forming-relationships-between-events
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use ruma_common::{EventId, MilliSecondsSinceUnixEpoch, UserId};
+
+use crate::relation::Annotation;
+
+/// The data from an `m.reaction` event necessary to aggregate reactions.
+#[derive(Debug, Clone, Copy)]
+#[allow(clippy::exhaustive_structs)]
+pub struct ReactionData<'a> {
+    /// The sender of the reaction.
+    pub sender: &'a UserId,
+
+    /// The annotation of the reaction.
+    pub annotation: &'a Annotation,
+}
+
+/// The data from an `m.replace` event necessary to find the latest edit of an event.
+#[derive(Debug, Clone, Copy)]
+#[allow(clippy::exhaustive_structs)]
+pub struct ReplacementData<'a> {
+    /// The ID of the replacement event.
+    pub event_id: &'a EventId,
+
+    /// The time of creation of the replacement event on the originating server.
+    pub origin_server_ts: MilliSecondsSinceUnixEpoch,
+
+    /// The ID of the event that is replaced.
+    pub replaces: &'a EventId,
+}
+
+/// Aggregate the given reactions by annotated event and key.
+///
+/// Several reactions from the same user with the same key are only counted once.
+///
+/// Returns a map of annotated event ID to a map of key to the set of user IDs that reacted with
+/// it. The number of reactions for a key is the length of its set.
+pub fn aggregate_reactions<'a>(
+    reactions: impl IntoIterator<Item = ReactionData<'a>>,
+) -> BTreeMap<&'a EventId, BTreeMap<&'a str, BTreeSet<&'a UserId>>> {
+    reactions.into_iter().fold(BTreeMap::new(), |mut acc, data| {
+        acc.entry(&*data.annotation.event_id)
+            .or_default()
+            .entry(data.annotation.key.as_str())
+            .or_default()
+            .insert(data.sender);
+        acc
+    })
+}
+
+/// Find the latest edit of each replaced event among the given replacements.
+///
+/// The replacements are ordered by `origin_server_ts`, and by event ID when their timestamps are
+/// identical, as required by the specification.
+///
+/// This doesn't check that the replacements are valid, i.e. that they have the same sender and
+/// type as the original event. Invalid replacements should be filtered out beforehand.
+///
+/// Returns a map of replaced event ID to the data of its latest replacement.
+pub fn latest_replacements<'a>(
+    replacements: impl IntoIterator<Item = ReplacementData<'a>>,
+) -> BTreeMap<&'a EventId, ReplacementData<'a>> {
+    replacements.into_iter().fold(BTreeMap::new(), |mut acc, data| {
+        acc.entry(data.replaces)
+            .and_modify(|latest: &mut ReplacementData<'a>| {
+                if (data.origin_server_ts, data.event_id)
+                    > (latest.origin_server_ts, latest.event_id)
+                {
+                    *latest = data;
+                }
+            })
+            .or_insert(data);
+        acc
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use js_int::uint;
+    use ruma_common::{event_id, owned_event_id, user_id, MilliSecondsSinceUnixEpoch};
+
+    use super::{aggregate_reactions, latest_replacements, ReactionData, ReplacementData};
+    use crate::relation::Annotation;
+
+    #[test]
+    fn reactions() {
+        let thumbs_up = Annotation::new(owned_event_id!("$original"), "👍".to_owned());
+        let heart = Annotation::new(owned_event_id!("$original"), "❤️".to_owned());
+        let other = Annotation::new(owned_event_id!("$other"), "👍".to_owned());
+
+        let alice = user_id!("@alice:localhost");
+        let bob = user_id!("@bob:localhost");
+
+        let aggregations = aggregate_reactions([
+            ReactionData { sender: alice, annotation: &thumbs_up },
+            ReactionData { sender: bob, annotation: &thumbs_up },
+            ReactionData { sender: alice, annotation: &thumbs_up },
+            ReactionData { sender: bob, annotation: &heart },
+            ReactionData { sender: alice, annotation: &other },
+        ]);
+
+        let original = &aggregations[event_id!("$original")];
+        assert_eq!(original.len(), 2);
+        assert_eq!(original["👍"].len(), 2);
+        assert_eq!(original["❤️"].iter().copied().collect::<Vec<_>>(), [bob]);
+
+        let other = &aggregations[event_id!("$other")];
+        assert_eq!(other["👍"].iter().copied().collect::<Vec<_>>(), [alice]);
+    }
+
+    #[test]
+    fn replacements() {
+        let original = event_id!("$original");
+
+        let latest = latest_replacements([
+            ReplacementData {
+                event_id: event_id!("$edit_b"),
+                origin_server_ts: MilliSecondsSinceUnixEpoch(uint!(2)),
+                replaces: original,
+            },
+            ReplacementData {
+                event_id: event_id!("$edit_c"),
+                origin_server_ts: MilliSecondsSinceUnixEpoch(uint!(1)),
+                replaces: original,
+            },
+            ReplacementData {
+                event_id: event_id!("$edit_a"),
+                origin_server_ts: MilliSecondsSinceUnixEpoch(uint!(2)),
+                replaces: original,
+            },
+        ]);
+
+        assert_eq!(latest.len(), 1);
+        assert_eq!(latest[original].event_id, event_id!("$edit_b"));
+    }
+}
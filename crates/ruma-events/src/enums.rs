@@ -1,6 +1,6 @@
 use ruma_common::{
-    serde::from_raw_json_value, EventId, MilliSecondsSinceUnixEpoch, OwnedRoomId, RoomId,
-    TransactionId, UserId,
+    serde::from_raw_json_value, EventId, MilliSecondsSinceUnixEpoch, MxcUri, OwnedRoomId,
+    RoomAliasId, RoomId, TransactionId, UserId,
 };
 #[cfg(feature = "unstable-msc3381")]
 use ruma_events::{
@@ -11,7 +11,7 @@ use ruma_macros::{event_enum, EventEnumFromEvent};
 use serde::{de, Deserialize};
 use serde_json::value::RawValue as RawJsonValue;
 
-use super::room::encrypted;
+use super::room::{encrypted, join_rules::JoinRule, member::MembershipState};
 
 /// Event types that servers should send as [stripped state] to help clients identify a room when
 /// they can't access the full room state.
@@ -319,6 +319,49 @@ impl From<AnyTimelineEvent> for AnySyncTimelineEvent {
     }
 }
 
+impl AnyStrippedStateEvent {
+    /// The name of the room, if this is an `m.room.name` event with a name.
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            Self::RoomName(ev) => ev.content.name.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// The URL of the avatar of the room, if this is an `m.room.avatar` event with a URL.
+    pub fn avatar_url(&self) -> Option<&MxcUri> {
+        match self {
+            Self::RoomAvatar(ev) => ev.content.url.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// The canonical alias of the room, if this is an `m.room.canonical_alias` event with an
+    /// alias.
+    pub fn canonical_alias(&self) -> Option<&RoomAliasId> {
+        match self {
+            Self::RoomCanonicalAlias(ev) => ev.content.alias.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// The join rule of the room, if this is an `m.room.join_rules` event.
+    pub fn join_rule(&self) -> Option<&JoinRule> {
+        match self {
+            Self::RoomJoinRules(ev) => Some(&ev.content.join_rule),
+            _ => None,
+        }
+    }
+
+    /// The membership of the given user, if this is the `m.room.member` event of that user.
+    pub fn membership(&self, user_id: &UserId) -> Option<&MembershipState> {
+        match self {
+            Self::RoomMember(ev) if *ev.state_key == *user_id => Some(&ev.content.membership),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Deserialize)]
 #[allow(clippy::exhaustive_structs)]
 struct EventDeHelper {
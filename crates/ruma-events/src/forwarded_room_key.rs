@@ -2,7 +2,9 @@
 //!
 //! [`m.forwarded_room_key`]: https://spec.matrix.org/latest/client-server-api/#mforwarded_room_key
 
-use ruma_common::{EventEncryptionAlgorithm, OwnedRoomId};
+use std::collections::BTreeMap;
+
+use ruma_common::{DeviceKeyAlgorithm, EventEncryptionAlgorithm, OwnedRoomId, RoomId};
 use ruma_macros::EventContent;
 use serde::{Deserialize, Serialize};
 
@@ -56,6 +58,63 @@ pub struct ToDeviceForwardedRoomKeyEventContent {
     pub shared_history: bool,
 }
 
+impl ToDeviceForwardedRoomKeyEventContent {
+    /// Whether this key is for the session with the given room ID, sender key and session ID.
+    ///
+    /// This should be checked before using this key to replace or complete an existing session.
+    pub fn is_for_session(&self, room_id: &RoomId, sender_key: &str, session_id: &str) -> bool {
+        self.room_id == room_id && self.sender_key == sender_key && self.session_id == session_id
+    }
+
+    /// Whether this key was forwarded by the device with the given Curve25519 key before being
+    /// sent to the current device.
+    ///
+    /// This doesn't include the device that sent this event.
+    pub fn was_forwarded_by(&self, curve25519_key: &str) -> bool {
+        self.forwarding_curve25519_key_chain.iter().any(|key| key == curve25519_key)
+    }
+
+    /// Prepare this key to be forwarded again to another device.
+    ///
+    /// `sender_curve25519_key` is the Curve25519 key of the device that sent this event. It is
+    /// added to the end of the forwarding chain.
+    pub fn forward(mut self, sender_curve25519_key: String) -> Self {
+        self.forwarding_curve25519_key_chain.push(sender_curve25519_key);
+        self
+    }
+
+    /// Creates a new `ToDeviceForwardedRoomKeyEventContent` from a room key in the key export
+    /// format.
+    ///
+    /// Returns `None` if the exported key doesn't have a claimed Ed25519 key.
+    pub fn from_exported_room_key(key: ExportedRoomKey) -> Option<Self> {
+        let ExportedRoomKey {
+            algorithm,
+            room_id,
+            sender_key,
+            session_id,
+            session_key,
+            mut sender_claimed_keys,
+            forwarding_curve25519_key_chain,
+        } = key;
+        let sender_claimed_ed25519_key =
+            sender_claimed_keys.remove(&DeviceKeyAlgorithm::Ed25519)?;
+
+        Some(
+            ToDeviceForwardedRoomKeyEventContentInit {
+                algorithm,
+                room_id,
+                sender_key,
+                session_id,
+                session_key,
+                sender_claimed_ed25519_key,
+                forwarding_curve25519_key_chain,
+            }
+            .into(),
+        )
+    }
+}
+
 /// Initial set of fields of `ToDeviceForwardedRoomKeyEventContent`.
 ///
 /// This struct will not be updated even if additional fields are added to `ConditionalPushRule` in
@@ -109,3 +168,184 @@ impl From<ToDeviceForwardedRoomKeyEventContentInit> for ToDeviceForwardedRoomKey
         }
     }
 }
+
+/// A room key in the [key export format].
+///
+/// [key export format]: https://spec.matrix.org/latest/client-server-api/#key-export-format
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(not(ruma_unstable_exhaustive_types), non_exhaustive)]
+pub struct ExportedRoomKey {
+    /// The encryption algorithm the key is to be used with.
+    pub algorithm: EventEncryptionAlgorithm,
+
+    /// The room where the key is used.
+    pub room_id: OwnedRoomId,
+
+    /// The Curve25519 key of the device which initiated the session originally.
+    pub sender_key: String,
+
+    /// The ID of the session that the key is for.
+    pub session_id: String,
+
+    /// The key for the session.
+    pub session_key: String,
+
+    /// The keys of the device which initiated the session originally, by algorithm.
+    ///
+    /// It is "claimed" because the device that exported the key has no way to tell that the
+    /// original room_key actually came from a device which owns the private part of these keys
+    /// unless it has done device verification.
+    pub sender_claimed_keys: BTreeMap<DeviceKeyAlgorithm, String>,
+
+    /// Chain of Curve25519 keys through which this session was forwarded, via
+    /// `m.forwarded_room_key` events.
+    pub forwarding_curve25519_key_chain: Vec<String>,
+}
+
+impl ExportedRoomKey {
+    /// Creates a new `ExportedRoomKey` with the given algorithm, room ID, sender key, session ID,
+    /// session key and claimed sender keys.
+    pub fn new(
+        algorithm: EventEncryptionAlgorithm,
+        room_id: OwnedRoomId,
+        sender_key: String,
+        session_id: String,
+        session_key: String,
+        sender_claimed_keys: BTreeMap<DeviceKeyAlgorithm, String>,
+    ) -> Self {
+        Self {
+            algorithm,
+            room_id,
+            sender_key,
+            session_id,
+            session_key,
+            sender_claimed_keys,
+            forwarding_curve25519_key_chain: Vec::new(),
+        }
+    }
+}
+
+impl From<ToDeviceForwardedRoomKeyEventContent> for ExportedRoomKey {
+    fn from(content: ToDeviceForwardedRoomKeyEventContent) -> Self {
+        Self {
+            algorithm: content.algorithm,
+            room_id: content.room_id,
+            sender_key: content.sender_key,
+            session_id: content.session_id,
+            session_key: content.session_key,
+            sender_claimed_keys: [(
+                DeviceKeyAlgorithm::Ed25519,
+                content.sender_claimed_ed25519_key,
+            )]
+            .into(),
+            forwarding_curve25519_key_chain: content.forwarding_curve25519_key_chain,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_common::{owned_room_id, room_id, DeviceKeyAlgorithm, EventEncryptionAlgorithm};
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+    use super::{
+        ExportedRoomKey, ToDeviceForwardedRoomKeyEventContent,
+        ToDeviceForwardedRoomKeyEventContentInit,
+    };
+
+    #[test]
+    fn session_and_forwarding_chain() {
+        let content =
+            ToDeviceForwardedRoomKeyEventContent::from(ToDeviceForwardedRoomKeyEventContentInit {
+                algorithm: EventEncryptionAlgorithm::MegolmV1AesSha2,
+                room_id: owned_room_id!("!Cuyf34gef24t:localhost"),
+                sender_key: "RF3s+E7RkTQTGF2d8Deol0FkQvgII2aJDf3/Jp5mxVU".to_owned(),
+                session_id: "X3lUlvLELLYxeTx4yOVu6UDpasGEVO0Jbu+QFnm0cKQ".to_owned(),
+                session_key: "AgAAAADxKHa9uFxcXzwYoNueL5Xqi69IkD4sni8Llf...".to_owned(),
+                sender_claimed_ed25519_key: "aj40p+aw64yPIdsxoog8jhPu9i7l7NcFRecuOQblE3Y"
+                    .to_owned(),
+                forwarding_curve25519_key_chain: vec![
+                    "hPQNcabIABgGnx3/ACv/jmMmiQHoeFfuLB17tzWp6Hw".to_owned(),
+                ],
+            });
+
+        assert!(content.is_for_session(
+            room_id!("!Cuyf34gef24t:localhost"),
+            "RF3s+E7RkTQTGF2d8Deol0FkQvgII2aJDf3/Jp5mxVU",
+            "X3lUlvLELLYxeTx4yOVu6UDpasGEVO0Jbu+QFnm0cKQ"
+        ));
+        assert!(!content.is_for_session(
+            room_id!("!other:localhost"),
+            "RF3s+E7RkTQTGF2d8Deol0FkQvgII2aJDf3/Jp5mxVU",
+            "X3lUlvLELLYxeTx4yOVu6UDpasGEVO0Jbu+QFnm0cKQ"
+        ));
+
+        assert!(content.was_forwarded_by("hPQNcabIABgGnx3/ACv/jmMmiQHoeFfuLB17tzWp6Hw"));
+        assert!(!content.was_forwarded_by("3C5BFWi2Y8MaVvjM8M22DBmh24PmgR0nPvJOIArzgyI"));
+
+        let forwarded = content.forward("3C5BFWi2Y8MaVvjM8M22DBmh24PmgR0nPvJOIArzgyI".to_owned());
+        assert_eq!(
+            forwarded.forwarding_curve25519_key_chain,
+            [
+                "hPQNcabIABgGnx3/ACv/jmMmiQHoeFfuLB17tzWp6Hw",
+                "3C5BFWi2Y8MaVvjM8M22DBmh24PmgR0nPvJOIArzgyI"
+            ]
+        );
+    }
+
+    #[test]
+    fn exported_room_key_round_trip() {
+        let json = json!({
+            "algorithm": "m.megolm.v1.aes-sha2",
+            "room_id": "!Cuyf34gef24t:localhost",
+            "sender_key": "RF3s+E7RkTQTGF2d8Deol0FkQvgII2aJDf3/Jp5mxVU",
+            "session_id": "X3lUlvLELLYxeTx4yOVu6UDpasGEVO0Jbu+QFnm0cKQ",
+            "session_key": "AgAAAADxKHa9uFxcXzwYoNueL5Xqi69IkD4sni8Llf...",
+            "sender_claimed_keys": {
+                "ed25519": "aj40p+aw64yPIdsxoog8jhPu9i7l7NcFRecuOQblE3Y",
+            },
+            "forwarding_curve25519_key_chain": [
+                "hPQNcabIABgGnx3/ACv/jmMmiQHoeFfuLB17tzWp6Hw",
+            ],
+        });
+
+        let exported = from_json_value::<ExportedRoomKey>(json.clone()).unwrap();
+        let content = ToDeviceForwardedRoomKeyEventContent::from_exported_room_key(exported)
+            .expect("exported key has an Ed25519 key");
+
+        assert_eq!(content.algorithm, EventEncryptionAlgorithm::MegolmV1AesSha2);
+        assert_eq!(content.room_id, "!Cuyf34gef24t:localhost");
+        assert_eq!(content.sender_key, "RF3s+E7RkTQTGF2d8Deol0FkQvgII2aJDf3/Jp5mxVU");
+        assert_eq!(content.session_id, "X3lUlvLELLYxeTx4yOVu6UDpasGEVO0Jbu+QFnm0cKQ");
+        assert_eq!(content.session_key, "AgAAAADxKHa9uFxcXzwYoNueL5Xqi69IkD4sni8Llf...");
+        assert_eq!(
+            content.sender_claimed_ed25519_key,
+            "aj40p+aw64yPIdsxoog8jhPu9i7l7NcFRecuOQblE3Y"
+        );
+        assert_eq!(
+            content.forwarding_curve25519_key_chain,
+            ["hPQNcabIABgGnx3/ACv/jmMmiQHoeFfuLB17tzWp6Hw"]
+        );
+
+        let exported = ExportedRoomKey::from(content);
+        assert_eq!(to_json_value(exported).unwrap(), json);
+    }
+
+    #[test]
+    fn exported_room_key_without_ed25519_key() {
+        let exported = ExportedRoomKey::new(
+            EventEncryptionAlgorithm::MegolmV1AesSha2,
+            owned_room_id!("!Cuyf34gef24t:localhost"),
+            "RF3s+E7RkTQTGF2d8Deol0FkQvgII2aJDf3/Jp5mxVU".to_owned(),
+            "X3lUlvLELLYxeTx4yOVu6UDpasGEVO0Jbu+QFnm0cKQ".to_owned(),
+            "AgAAAADxKHa9uFxcXzwYoNueL5Xqi69IkD4sni8Llf...".to_owned(),
+            [(
+                DeviceKeyAlgorithm::Curve25519,
+                "hPQNcabIABgGnx3/ACv/jmMmiQHoeFfuLB17tzWp6Hw".to_owned(),
+            )]
+            .into(),
+        );
+
+        assert!(ToDeviceForwardedRoomKeyEventContent::from_exported_room_key(exported).is_none());
+    }
+}
@@ -21,6 +21,9 @@ pub mod mac;
 pub mod ready;
 pub mod request;
 pub mod start;
+mod state;
+
+pub use self::state::{InvalidVerificationTransition, VerificationState, VerificationStep};
 
 // For these two constants, see <https://spec.matrix.org/latest/client-server-api/#key-verification-framework>
 /// The amount of time after which a verification request should be ignored, relative to its
@@ -0,0 +1,300 @@
+use super::VerificationMethod;
+use crate::{MessageLikeEventType, ToDeviceEventType};
+
+/// A step of the [key verification framework], corresponding to one of the
+/// `m.key.verification.*` events.
+///
+/// [key verification framework]: https://spec.matrix.org/latest/client-server-api/#key-verification-framework
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(not(ruma_unstable_exhaustive_types), non_exhaustive)]
+pub enum VerificationStep {
+    /// An `m.key.verification.request` event.
+    Request,
+
+    /// An `m.key.verification.ready` event.
+    Ready,
+
+    /// An `m.key.verification.start` event.
+    Start,
+
+    /// An `m.key.verification.accept` event.
+    Accept,
+
+    /// An `m.key.verification.key` event.
+    Key,
+
+    /// An `m.key.verification.mac` event.
+    Mac,
+
+    /// An `m.key.verification.done` event.
+    Done,
+
+    /// An `m.key.verification.cancel` event.
+    Cancel,
+}
+
+impl VerificationStep {
+    /// Get the step corresponding to the given to-device event type, if it is a key verification
+    /// event.
+    pub fn from_event_type(event_type: &ToDeviceEventType) -> Option<Self> {
+        Some(match event_type {
+            ToDeviceEventType::KeyVerificationRequest => Self::Request,
+            ToDeviceEventType::KeyVerificationReady => Self::Ready,
+            ToDeviceEventType::KeyVerificationStart => Self::Start,
+            ToDeviceEventType::KeyVerificationAccept => Self::Accept,
+            ToDeviceEventType::KeyVerificationKey => Self::Key,
+            ToDeviceEventType::KeyVerificationMac => Self::Mac,
+            ToDeviceEventType::KeyVerificationDone => Self::Done,
+            ToDeviceEventType::KeyVerificationCancel => Self::Cancel,
+            _ => return None,
+        })
+    }
+
+    /// Get the step corresponding to the given message-like event type, if it is a key
+    /// verification event.
+    ///
+    /// In rooms, the verification is requested with an `m.room.message` event with the
+    /// `m.key.verification.request` msgtype, so this never returns [`VerificationStep::Request`].
+    pub fn from_message_like_event_type(event_type: &MessageLikeEventType) -> Option<Self> {
+        Some(match event_type {
+            MessageLikeEventType::KeyVerificationReady => Self::Ready,
+            MessageLikeEventType::KeyVerificationStart => Self::Start,
+            MessageLikeEventType::KeyVerificationAccept => Self::Accept,
+            MessageLikeEventType::KeyVerificationKey => Self::Key,
+            MessageLikeEventType::KeyVerificationMac => Self::Mac,
+            MessageLikeEventType::KeyVerificationDone => Self::Done,
+            MessageLikeEventType::KeyVerificationCancel => Self::Cancel,
+            _ => return None,
+        })
+    }
+}
+
+/// The state of a key verification flow, as seen from the events exchanged by both devices.
+///
+/// This only checks that the events are received in an order allowed by the specification. It
+/// doesn't check their content or which device sent them.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(not(ruma_unstable_exhaustive_types), non_exhaustive)]
+pub enum VerificationState {
+    /// No event was exchanged yet.
+    #[default]
+    Initial,
+
+    /// The verification was requested.
+    Requested,
+
+    /// The verification request was accepted.
+    Ready,
+
+    /// A verification method was started.
+    Started {
+        /// The method that was started.
+        method: VerificationMethod,
+    },
+
+    /// The start of the SAS verification was accepted.
+    Accepted,
+
+    /// One of the devices sent its ephemeral public key.
+    KeySent,
+
+    /// Both devices sent their ephemeral public key.
+    KeysExchanged,
+
+    /// One of the devices sent its MAC.
+    MacSent,
+
+    /// Both devices sent their MAC.
+    MacsExchanged,
+
+    /// At least one of the devices marked the verification as done.
+    ///
+    /// Both devices send an `m.key.verification.done` event, so this state accepts another
+    /// [`VerificationStep::Done`].
+    Done,
+
+    /// The verification was cancelled.
+    Cancelled,
+}
+
+impl VerificationState {
+    /// Create a new `VerificationState` with no event exchanged yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the verification flow is over, successfully or not.
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Done | Self::Cancelled)
+    }
+
+    /// Apply the given step to this state.
+    ///
+    /// `method` must be set to the method of the `m.key.verification.start` event for
+    /// [`VerificationStep::Start`]. It is ignored for the other steps.
+    ///
+    /// Returns an error if the step is not allowed in the current state. In this case, the
+    /// verification should be cancelled with the `m.unexpected_message` code, and the state is
+    /// left unchanged.
+    pub fn apply(
+        &mut self,
+        step: VerificationStep,
+        method: Option<&VerificationMethod>,
+    ) -> Result<(), InvalidVerificationTransition> {
+        let new_state = match (&*self, step) {
+            (Self::Done, VerificationStep::Done) => Some(Self::Done),
+            (Self::Done | Self::Cancelled, _) => None,
+            (_, VerificationStep::Cancel) => Some(Self::Cancelled),
+
+            (Self::Initial, VerificationStep::Request) => Some(Self::Requested),
+            (Self::Requested, VerificationStep::Ready) => Some(Self::Ready),
+
+            // Both devices can send a start event, in which case only one of them is kept. The
+            // to-device flow can also begin directly with a start event.
+            (Self::Initial | Self::Ready | Self::Started { .. }, VerificationStep::Start) => {
+                method.map(|method| Self::Started { method: method.clone() })
+            }
+
+            // SAS verification.
+            (Self::Started { method: VerificationMethod::SasV1 }, VerificationStep::Accept) => {
+                Some(Self::Accepted)
+            }
+            (Self::Accepted, VerificationStep::Key) => Some(Self::KeySent),
+            (Self::KeySent, VerificationStep::Key) => Some(Self::KeysExchanged),
+            (Self::KeysExchanged, VerificationStep::Mac) => Some(Self::MacSent),
+            (Self::MacSent, VerificationStep::Mac) => Some(Self::MacsExchanged),
+            (Self::MacsExchanged, VerificationStep::Done) => Some(Self::Done),
+
+            // Other methods, like QR code reciprocation, go straight from start to done.
+            (Self::Started { method: started_method }, VerificationStep::Done)
+                if *started_method != VerificationMethod::SasV1 =>
+            {
+                Some(Self::Done)
+            }
+
+            _ => None,
+        };
+
+        match new_state {
+            Some(new_state) => {
+                *self = new_state;
+                Ok(())
+            }
+            None => Err(InvalidVerificationTransition { state: self.clone(), step }),
+        }
+    }
+}
+
+/// An error encountered when applying a [`VerificationStep`] that is not allowed in the current
+/// [`VerificationState`].
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("unexpected verification step {step:?} in state {state:?}")]
+#[non_exhaustive]
+pub struct InvalidVerificationTransition {
+    /// The state of the verification.
+    pub state: VerificationState,
+
+    /// The step that is not allowed in this state.
+    pub step: VerificationStep,
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches2::assert_matches;
+
+    use super::{InvalidVerificationTransition, VerificationState, VerificationStep};
+    use crate::{key::verification::VerificationMethod, MessageLikeEventType, ToDeviceEventType};
+
+    #[test]
+    fn sas_flow() {
+        let mut state = VerificationState::new();
+
+        state.apply(VerificationStep::Request, None).unwrap();
+        state.apply(VerificationStep::Ready, None).unwrap();
+        state.apply(VerificationStep::Start, Some(&VerificationMethod::SasV1)).unwrap();
+        state.apply(VerificationStep::Accept, None).unwrap();
+        state.apply(VerificationStep::Key, None).unwrap();
+        state.apply(VerificationStep::Key, None).unwrap();
+        state.apply(VerificationStep::Mac, None).unwrap();
+        state.apply(VerificationStep::Mac, None).unwrap();
+        state.apply(VerificationStep::Done, None).unwrap();
+        assert_eq!(state, VerificationState::Done);
+        state.apply(VerificationStep::Done, None).unwrap();
+
+        assert!(state.is_finished());
+    }
+
+    #[test]
+    fn qr_code_flow() {
+        let mut state = VerificationState::new();
+
+        state.apply(VerificationStep::Request, None).unwrap();
+        state.apply(VerificationStep::Ready, None).unwrap();
+        state.apply(VerificationStep::Start, Some(&VerificationMethod::ReciprocateV1)).unwrap();
+        state.apply(VerificationStep::Done, None).unwrap();
+        state.apply(VerificationStep::Done, None).unwrap();
+
+        assert_eq!(state, VerificationState::Done);
+    }
+
+    #[test]
+    fn sas_flow_requires_both_keys_and_macs() {
+        let mut state = VerificationState::new();
+
+        state.apply(VerificationStep::Start, Some(&VerificationMethod::SasV1)).unwrap();
+        state.apply(VerificationStep::Accept, None).unwrap();
+        state.apply(VerificationStep::Key, None).unwrap();
+        assert_eq!(state, VerificationState::KeySent);
+        state.apply(VerificationStep::Mac, None).unwrap_err();
+
+        state.apply(VerificationStep::Key, None).unwrap();
+        state.apply(VerificationStep::Key, None).unwrap_err();
+        state.apply(VerificationStep::Mac, None).unwrap();
+        assert_eq!(state, VerificationState::MacSent);
+        state.apply(VerificationStep::Done, None).unwrap_err();
+
+        state.apply(VerificationStep::Mac, None).unwrap();
+        state.apply(VerificationStep::Done, None).unwrap();
+        assert_eq!(state, VerificationState::Done);
+    }
+
+    #[test]
+    fn step_from_event_type() {
+        assert_eq!(
+            VerificationStep::from_event_type(&ToDeviceEventType::KeyVerificationRequest),
+            Some(VerificationStep::Request)
+        );
+        assert_eq!(VerificationStep::from_event_type(&ToDeviceEventType::RoomKey), None);
+        assert_eq!(
+            VerificationStep::from_message_like_event_type(
+                &MessageLikeEventType::KeyVerificationMac
+            ),
+            Some(VerificationStep::Mac)
+        );
+        assert_eq!(
+            VerificationStep::from_message_like_event_type(&MessageLikeEventType::RoomMessage),
+            None
+        );
+    }
+
+    #[test]
+    fn invalid_transitions() {
+        let mut state = VerificationState::new();
+
+        state.apply(VerificationStep::Request, None).unwrap();
+        assert_matches!(
+            state.apply(VerificationStep::Key, None),
+            Err(InvalidVerificationTransition { state: VerificationState::Requested, step })
+        );
+        assert_eq!(step, VerificationStep::Key);
+
+        state.apply(VerificationStep::Ready, None).unwrap();
+        state.apply(VerificationStep::Start, Some(&VerificationMethod::SasV1)).unwrap();
+        state.apply(VerificationStep::Done, None).unwrap_err();
+
+        state.apply(VerificationStep::Cancel, None).unwrap();
+        assert!(state.is_finished());
+        state.apply(VerificationStep::Start, Some(&VerificationMethod::SasV1)).unwrap_err();
+        assert_eq!(state, VerificationState::Cancelled);
+    }
+}
@@ -137,6 +137,7 @@ pub mod macros {
     pub use ruma_macros::{Event, EventContent};
 }
 
+pub mod aggregations;
 #[cfg(feature = "unstable-msc3927")]
 pub mod audio;
 #[cfg(feature = "unstable-msc3489")]
@@ -1,6 +1,6 @@
 //! Modules and types for events in the `m.policy.rule` namespace.
 
-use ruma_common::serde::StringEnum;
+use ruma_common::{glob, serde::StringEnum};
 use serde::{Deserialize, Serialize};
 
 use crate::PrivOwnedStr;
@@ -31,6 +31,40 @@ impl PolicyRuleEventContent {
     pub fn new(entity: String, recommendation: Recommendation, reason: String) -> Self {
         Self { entity, recommendation, reason }
     }
+
+    /// Whether the given entity is affected by this rule.
+    ///
+    /// The entity is matched against the glob in the `entity` field, case sensitively.
+    pub fn matches(&self, entity: &str) -> bool {
+        glob::matches(&self.entity, entity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PolicyRuleEventContent, Recommendation};
+
+    #[test]
+    fn entity_matches() {
+        let content = PolicyRuleEventContent::new(
+            "*.evil.example".to_owned(),
+            Recommendation::Ban,
+            "spam".to_owned(),
+        );
+
+        assert!(content.matches("matrix.evil.example"));
+        assert!(!content.matches("evil.example"));
+        assert!(!content.matches("matrix.EVIL.example"));
+
+        let content = PolicyRuleEventContent::new(
+            "@spammer?:example.org".to_owned(),
+            Recommendation::Ban,
+            "spam".to_owned(),
+        );
+
+        assert!(content.matches("@spammer1:example.org"));
+        assert!(!content.matches("@spammer:example.org"));
+    }
 }
 
 /// The possibly redacted form of [`PolicyRuleEventContent`].
@@ -3,8 +3,12 @@
 //! The only content valid for this event is `PresenceEventContent`.
 
 use js_int::UInt;
-use ruma_common::{presence::PresenceState, OwnedMxcUri, OwnedUserId};
+use ruma_common::{
+    presence::{LastActive, PresenceState},
+    MilliSecondsSinceUnixEpoch, OwnedMxcUri, OwnedUserId,
+};
 use serde::{Deserialize, Serialize};
+use web_time::Duration;
 
 /// Presence event.
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -67,12 +71,27 @@ impl PresenceEventContent {
             status_msg: None,
         }
     }
+
+    /// Computes when the user was last active.
+    ///
+    /// `received_at` should be the time when this event was received.
+    pub fn last_active(&self, received_at: MilliSecondsSinceUnixEpoch) -> LastActive {
+        LastActive::new(
+            self.currently_active,
+            self.last_active_ago.map(|ago| Duration::from_millis(ago.into())),
+            received_at,
+        )
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use js_int::uint;
-    use ruma_common::{mxc_uri, presence::PresenceState};
+    use ruma_common::{
+        mxc_uri,
+        presence::{LastActive, PresenceState},
+        MilliSecondsSinceUnixEpoch,
+    };
     use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
     use super::{PresenceEvent, PresenceEventContent};
@@ -124,6 +143,10 @@ mod tests {
         assert_eq!(ev.content.presence, PresenceState::Online);
         assert_eq!(ev.content.status_msg.as_deref(), Some("Making cupcakes"));
         assert_eq!(ev.sender, "@example:localhost");
+        assert_eq!(
+            ev.content.last_active(MilliSecondsSinceUnixEpoch(uint!(3_000_000))),
+            LastActive::At(MilliSecondsSinceUnixEpoch(uint!(521_407)))
+        );
 
         #[cfg(feature = "compat-empty-string-null")]
         {
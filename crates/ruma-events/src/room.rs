@@ -16,6 +16,7 @@ pub mod aliases;
 pub mod avatar;
 pub mod canonical_alias;
 pub mod create;
+pub mod display_name;
 pub mod encrypted;
 pub mod encryption;
 pub mod guest_access;
@@ -0,0 +1,208 @@
+//! Helpers to calculate the [display name of a room].
+//!
+//! [display name of a room]: https://spec.matrix.org/latest/client-server-api/#calculating-the-display-name-for-a-room
+
+use std::{collections::BTreeMap, fmt};
+
+use ruma_common::{OwnedRoomAliasId, OwnedUserId};
+
+use super::{canonical_alias::RoomCanonicalAliasEventContent, name::RoomNameEventContent};
+
+/// A member of a room used to calculate the room's display name.
+///
+/// These are usually the `m.heroes` of the room summary returned by `/sync`, along with the
+/// `displayname` of their `m.room.member` event, if any.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[allow(clippy::exhaustive_structs)]
+pub struct RoomHero {
+    /// The ID of the user.
+    pub user_id: OwnedUserId,
+
+    /// The display name of the user in the room, if any.
+    pub display_name: Option<String>,
+}
+
+impl RoomHero {
+    /// Creates a new `RoomHero` with the given user ID and display name.
+    pub fn new(user_id: OwnedUserId, display_name: Option<String>) -> Self {
+        Self { user_id, display_name }
+    }
+}
+
+/// The display name of a room, calculated according to the rules of the Matrix specification.
+///
+/// The [`Display`](fmt::Display) implementation uses the English strings suggested by the
+/// specification. Clients that want to localize the name can match on the variants instead.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(not(ruma_unstable_exhaustive_types), non_exhaustive)]
+pub enum RoomDisplayName {
+    /// The name of the room, from the `m.room.name` state event.
+    Named(String),
+
+    /// The canonical alias of the room, from the `m.room.canonical_alias` state event.
+    Aliased(OwnedRoomAliasId),
+
+    /// A name computed from the heroes of the room, like `Alice, Bob and 3 others`.
+    Calculated(String),
+
+    /// The room is empty, but it used to contain the heroes, like `Alice and Bob`.
+    EmptyWas(String),
+
+    /// The room is empty and there are no heroes.
+    Empty,
+}
+
+impl RoomDisplayName {
+    /// Calculate the display name of a room.
+    ///
+    /// The `heroes` should not contain the user for whom the name is calculated. The member
+    /// counts are the `m.joined_member_count` and `m.invited_member_count` of the room summary,
+    /// which include the user.
+    ///
+    /// Heroes sharing the same display name are disambiguated with their user ID.
+    pub fn calculate(
+        name: Option<&RoomNameEventContent>,
+        canonical_alias: Option<&RoomCanonicalAliasEventContent>,
+        heroes: &[RoomHero],
+        joined_member_count: u64,
+        invited_member_count: u64,
+    ) -> Self {
+        if let Some(name) = name.map(|content| content.name.trim()).filter(|name| !name.is_empty())
+        {
+            return Self::Named(name.to_owned());
+        }
+
+        if let Some(alias) = canonical_alias.and_then(|content| content.alias.clone()) {
+            return Self::Aliased(alias);
+        }
+
+        let names = hero_names(heroes);
+        if names.is_empty() {
+            return Self::Empty;
+        }
+
+        let total_count = joined_member_count.saturating_add(invited_member_count);
+        if total_count <= 1 {
+            Self::EmptyWas(join_names(&names, 0))
+        } else {
+            let others = (total_count - 1).saturating_sub(names.len() as u64);
+            Self::Calculated(join_names(&names, others))
+        }
+    }
+}
+
+impl fmt::Display for RoomDisplayName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Named(name) | Self::Calculated(name) => f.write_str(name),
+            Self::Aliased(alias) => f.write_str(alias.as_str()),
+            Self::EmptyWas(names) => write!(f, "Empty Room (was {names})"),
+            Self::Empty => f.write_str("Empty Room"),
+        }
+    }
+}
+
+/// Get the display names of the heroes, disambiguated if necessary.
+fn hero_names(heroes: &[RoomHero]) -> Vec<String> {
+    let mut name_counts = BTreeMap::<&str, usize>::new();
+    for name in heroes.iter().filter_map(|hero| hero.display_name.as_deref()) {
+        *name_counts.entry(name).or_default() += 1;
+    }
+
+    heroes
+        .iter()
+        .map(|hero| match hero.display_name.as_deref().filter(|name| !name.is_empty()) {
+            Some(name) if name_counts.get(name).is_some_and(|count| *count > 1) => {
+                format!("{name} ({})", hero.user_id)
+            }
+            Some(name) => name.to_owned(),
+            None => hero.user_id.to_string(),
+        })
+        .collect()
+}
+
+/// Join the names in an English list, with the given number of other members at the end.
+fn join_names(names: &[String], others: u64) -> String {
+    let mut parts = names.to_vec();
+    match others {
+        0 => {}
+        1 => parts.push("1 other".to_owned()),
+        n => parts.push(format!("{n} others")),
+    }
+
+    match parts.split_last() {
+        None => String::new(),
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} and {last}", rest.join(", ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_common::{owned_room_alias_id, owned_user_id};
+
+    use super::{RoomDisplayName, RoomHero};
+    use crate::room::{
+        canonical_alias::RoomCanonicalAliasEventContent, name::RoomNameEventContent,
+    };
+
+    fn alice() -> RoomHero {
+        RoomHero::new(owned_user_id!("@alice:example.org"), Some("Alice".to_owned()))
+    }
+
+    fn bob() -> RoomHero {
+        RoomHero::new(owned_user_id!("@bob:example.org"), Some("Bob".to_owned()))
+    }
+
+    #[test]
+    fn name_and_alias() {
+        let name = RoomNameEventContent::new("The room".to_owned());
+        let mut alias = RoomCanonicalAliasEventContent::new();
+        alias.alias = Some(owned_room_alias_id!("#room:example.org"));
+
+        let display_name = RoomDisplayName::calculate(Some(&name), Some(&alias), &[alice()], 2, 0);
+        assert_eq!(display_name, RoomDisplayName::Named("The room".to_owned()));
+
+        let empty_name = RoomNameEventContent::new("  ".to_owned());
+        let display_name =
+            RoomDisplayName::calculate(Some(&empty_name), Some(&alias), &[alice()], 2, 0);
+        assert_eq!(display_name.to_string(), "#room:example.org");
+    }
+
+    #[test]
+    fn heroes() {
+        let display_name = RoomDisplayName::calculate(None, None, &[alice()], 2, 0);
+        assert_eq!(display_name.to_string(), "Alice");
+
+        let display_name = RoomDisplayName::calculate(None, None, &[alice(), bob()], 2, 1);
+        assert_eq!(display_name.to_string(), "Alice and Bob");
+
+        let display_name = RoomDisplayName::calculate(None, None, &[alice(), bob()], 10, 2);
+        assert_eq!(display_name.to_string(), "Alice, Bob and 9 others");
+
+        let unnamed = RoomHero::new(owned_user_id!("@carl:example.org"), None);
+        let display_name = RoomDisplayName::calculate(None, None, &[alice(), unnamed], 3, 0);
+        assert_eq!(display_name.to_string(), "Alice and @carl:example.org");
+    }
+
+    #[test]
+    fn disambiguated_heroes() {
+        let other_alice =
+            RoomHero::new(owned_user_id!("@alice:example.com"), Some("Alice".to_owned()));
+        let display_name = RoomDisplayName::calculate(None, None, &[alice(), other_alice], 3, 0);
+        assert_eq!(
+            display_name.to_string(),
+            "Alice (@alice:example.org) and Alice (@alice:example.com)"
+        );
+    }
+
+    #[test]
+    fn empty_room() {
+        let display_name = RoomDisplayName::calculate(None, None, &[], 1, 0);
+        assert_eq!(display_name, RoomDisplayName::Empty);
+        assert_eq!(display_name.to_string(), "Empty Room");
+
+        let display_name = RoomDisplayName::calculate(None, None, &[alice(), bob()], 1, 0);
+        assert_eq!(display_name.to_string(), "Empty Room (was Alice and Bob)");
+    }
+}
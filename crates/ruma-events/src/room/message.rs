@@ -58,7 +58,7 @@ pub use self::{
     file::{FileInfo, FileMessageEventContent},
     image::ImageMessageEventContent,
     key_verification_request::KeyVerificationRequestEventContent,
-    location::{LocationInfo, LocationMessageEventContent},
+    location::{GeoUri, GeoUriError, LocationInfo, LocationMessageEventContent},
     notice::NoticeMessageEventContent,
     relation::{Relation, RelationWithoutReplacement},
     relation_serde::deserialize_relation,
@@ -116,6 +116,15 @@ impl AudioMessageEventContent {
     pub fn formatted_caption(&self) -> Option<&FormattedBody> {
         formatted_caption(&self.body, self.formatted.as_ref(), self.filename.as_deref())
     }
+
+    /// Whether this audio message is a voice message, according to the
+    /// [first version of MSC3245][msc].
+    ///
+    /// [msc]: https://github.com/matrix-org/matrix-spec-proposals/blob/83f6c5b469c1d78f714e335dcaa25354b255ffa5/proposals/3245-voice-messages.md
+    #[cfg(feature = "unstable-msc3245-v1-compat")]
+    pub fn is_voice_message(&self) -> bool {
+        self.voice.is_some()
+    }
 }
 
 /// Metadata about an audio clip.
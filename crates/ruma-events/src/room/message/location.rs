@@ -1,3 +1,5 @@
+use std::{fmt, str::FromStr};
+
 #[cfg(feature = "unstable-msc3488")]
 use ruma_common::MilliSecondsSinceUnixEpoch;
 use serde::{Deserialize, Serialize};
@@ -94,6 +96,13 @@ impl LocationMessageEventContent {
         &self.geo_uri
     }
 
+    /// Parse the `geo:` URI of this `LocationMessageEventContent`.
+    ///
+    /// This uses the same URI as [`Self::geo_uri()`].
+    pub fn parse_geo_uri(&self) -> Result<GeoUri, GeoUriError> {
+        self.geo_uri().parse()
+    }
+
     /// Get the plain text representation of this `LocationMessageEventContent`.
     pub fn plain_text_representation(&self) -> &str {
         #[cfg(feature = "unstable-msc3488")]
@@ -134,3 +143,127 @@ impl LocationInfo {
         Self::default()
     }
 }
+
+/// A parsed `geo:` URI.
+///
+/// Only the WGS-84 coordinate reference system is supported. Parameters other than `crs` and `u`
+/// are ignored.
+///
+/// See [RFC 5870](https://datatracker.ietf.org/doc/html/rfc5870) for more details.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(not(ruma_unstable_exhaustive_types), non_exhaustive)]
+pub struct GeoUri {
+    /// The latitude, in decimal degrees.
+    pub latitude: f64,
+
+    /// The longitude, in decimal degrees.
+    pub longitude: f64,
+
+    /// The altitude, in meters.
+    pub altitude: Option<f64>,
+
+    /// The uncertainty of the location, in meters.
+    pub uncertainty: Option<f64>,
+}
+
+impl GeoUri {
+    /// Creates a new `GeoUri` with the given latitude and longitude.
+    ///
+    /// Returns an error if the latitude is not between -90 and 90, or if the longitude is not
+    /// between -180 and 180.
+    pub fn new(latitude: f64, longitude: f64) -> Result<Self, GeoUriError> {
+        if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+            return Err(GeoUriError::InvalidCoordinates);
+        }
+
+        Ok(Self { latitude, longitude, altitude: None, uncertainty: None })
+    }
+}
+
+impl FromStr for GeoUri {
+    type Err = GeoUriError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rest = s
+            .get(..4)
+            .filter(|scheme| scheme.eq_ignore_ascii_case("geo:"))
+            .map(|_| &s[4..])
+            .ok_or(GeoUriError::MissingScheme)?;
+
+        let mut parts = rest.split(';');
+        let coordinates = parts
+            .next()
+            .unwrap_or_default()
+            .split(',')
+            .map(|c| {
+                c.parse::<f64>()
+                    .ok()
+                    .filter(|v| v.is_finite())
+                    .ok_or(GeoUriError::InvalidCoordinates)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut geo_uri = match *coordinates {
+            [latitude, longitude] => Self::new(latitude, longitude)?,
+            [latitude, longitude, altitude] => {
+                Self { altitude: Some(altitude), ..Self::new(latitude, longitude)? }
+            }
+            _ => return Err(GeoUriError::InvalidCoordinates),
+        };
+
+        for param in parts {
+            let (name, value) = param.split_once('=').unwrap_or((param, ""));
+
+            if name.eq_ignore_ascii_case("crs") {
+                if !value.eq_ignore_ascii_case("wgs84") {
+                    return Err(GeoUriError::UnsupportedCrs);
+                }
+            } else if name.eq_ignore_ascii_case("u") {
+                let uncertainty = value
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|u| u.is_finite() && *u >= 0.0)
+                    .ok_or(GeoUriError::InvalidUncertainty)?;
+                geo_uri.uncertainty = Some(uncertainty);
+            }
+        }
+
+        Ok(geo_uri)
+    }
+}
+
+impl fmt::Display for GeoUri {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "geo:{},{}", self.latitude, self.longitude)?;
+
+        if let Some(altitude) = self.altitude {
+            write!(f, ",{altitude}")?;
+        }
+        if let Some(uncertainty) = self.uncertainty {
+            write!(f, ";u={uncertainty}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// An error encountered when trying to parse a [`GeoUri`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum GeoUriError {
+    /// The URI doesn't start with `geo:`.
+    #[error("missing `geo:` scheme")]
+    MissingScheme,
+
+    /// The coordinates are missing, malformed or out of range.
+    #[error("invalid coordinates")]
+    InvalidCoordinates,
+
+    /// The coordinate reference system is not WGS-84.
+    #[error("unsupported coordinate reference system")]
+    UnsupportedCrs,
+
+    /// The `u` parameter is not a positive number.
+    #[error("invalid uncertainty")]
+    InvalidUncertainty,
+}
@@ -2,10 +2,9 @@
 //!
 //! [`m.room.server_acl`]: https://spec.matrix.org/latest/client-server-api/#mroomserver_acl
 
-use ruma_common::ServerName;
+use ruma_common::{glob, ServerName};
 use ruma_macros::EventContent;
 use serde::{Deserialize, Serialize};
-use wildmatch::WildMatch;
 
 use crate::EmptyStateKey;
 
@@ -61,8 +60,8 @@ impl RoomServerAclEventContent {
 
         let host = server_name.host();
 
-        self.deny.iter().all(|d| !WildMatch::new(d).matches(host))
-            && self.allow.iter().any(|a| WildMatch::new(a).matches(host))
+        self.deny.iter().all(|d| !glob::matches(d, host))
+            && self.allow.iter().any(|a| glob::matches(a, host))
     }
 }
 
@@ -190,6 +190,19 @@ fn message_event_deserialization() {
     assert_eq!(formatted.body, "<strong>baba</strong>");
 }
 
+#[test]
+fn message_event_try_from_enum() {
+    let event = from_json_value::<AnySyncMessageLikeEvent>(message_event_sync()).unwrap();
+
+    let event = SyncMessageLikeEvent::<RoomMessageEventContent>::try_from(event).unwrap();
+    assert_matches!(event, SyncMessageLikeEvent::Original(ev));
+    assert_eq!(ev.content.body(), "baba");
+
+    let event = from_json_value::<AnySyncStateEvent>(aliases_event_sync()).unwrap();
+    let event = SyncStateEvent::<RoomPowerLevelsEventContent>::try_from(event).unwrap_err();
+    assert_matches!(event, AnySyncStateEvent::RoomAliases(_));
+}
+
 #[test]
 fn message_event_serialization() {
     let content = RoomMessageEventContent::text_plain("test");
@@ -14,10 +14,10 @@ use ruma_events::{
     room::{
         message::{
             AddMentions, AudioMessageEventContent, EmoteMessageEventContent,
-            FileMessageEventContent, FormattedBody, ForwardThread, ImageMessageEventContent,
-            KeyVerificationRequestEventContent, MessageType, OriginalRoomMessageEvent,
-            OriginalSyncRoomMessageEvent, Relation, ReplyWithinThread, RoomMessageEventContent,
-            TextMessageEventContent, VideoMessageEventContent,
+            FileMessageEventContent, FormattedBody, ForwardThread, GeoUri, GeoUriError,
+            ImageMessageEventContent, KeyVerificationRequestEventContent, MessageType,
+            OriginalRoomMessageEvent, OriginalSyncRoomMessageEvent, Relation, ReplyWithinThread,
+            RoomMessageEventContent, TextMessageEventContent, VideoMessageEventContent,
         },
         EncryptedFileInit, JsonWebKeyInit, MediaSource,
     },
@@ -487,6 +487,31 @@ fn audio_msgtype_deserialization() {
     assert!(content.caption().is_none());
 }
 
+#[test]
+#[cfg(feature = "unstable-msc3245-v1-compat")]
+fn voice_msgtype_deserialization() {
+    use std::time::Duration;
+
+    let json_data = json!({
+        "body": "Voice message",
+        "url": "mxc://notareal.hs/file",
+        "msgtype": "m.audio",
+        "org.matrix.msc1767.audio": {
+            "duration": 5300,
+            "waveform": [0, 256, 1024],
+        },
+        "org.matrix.msc3245.voice": {},
+    });
+
+    let event_content = from_json_value::<RoomMessageEventContent>(json_data).unwrap();
+    assert_matches!(event_content.msgtype, MessageType::Audio(content));
+    assert!(content.is_voice_message());
+
+    let audio = content.audio.unwrap();
+    assert_eq!(audio.duration, Duration::from_millis(5300));
+    assert_eq!(audio.waveform.len(), 3);
+}
+
 #[test]
 fn file_msgtype_plain_content_serialization() {
     let message_event_content =
@@ -800,6 +825,33 @@ fn location_msgtype_deserialization() {
     assert_matches!(event_content.msgtype, MessageType::Location(content));
     assert_eq!(content.body, "Alice was at geo:51.5008,0.1247;u=35");
     assert_eq!(content.geo_uri, "geo:51.5008,0.1247;u=35");
+
+    let geo_uri = content.parse_geo_uri().unwrap();
+    assert_eq!(geo_uri.latitude, 51.5008);
+    assert_eq!(geo_uri.longitude, 0.1247);
+    assert_eq!(geo_uri.altitude, None);
+    assert_eq!(geo_uri.uncertainty, Some(35.0));
+}
+
+#[test]
+fn geo_uri_parsing() {
+    let geo_uri = "GEO:-33.8688,151.2093,58;crs=wgs84;u=12.5;foo=bar".parse::<GeoUri>().unwrap();
+    assert_eq!(geo_uri.latitude, -33.8688);
+    assert_eq!(geo_uri.longitude, 151.2093);
+    assert_eq!(geo_uri.altitude, Some(58.0));
+    assert_eq!(geo_uri.uncertainty, Some(12.5));
+    assert_eq!(geo_uri.to_string(), "geo:-33.8688,151.2093,58;u=12.5");
+
+    assert_eq!("51.5008,0.1247".parse::<GeoUri>(), Err(GeoUriError::MissingScheme));
+    assert_eq!("geo:91,0".parse::<GeoUri>(), Err(GeoUriError::InvalidCoordinates));
+    assert_eq!("geo:51.5008".parse::<GeoUri>(), Err(GeoUriError::InvalidCoordinates));
+    assert_eq!("geo:51.5008,0.1247;crs=moon".parse::<GeoUri>(), Err(GeoUriError::UnsupportedCrs));
+    assert_eq!("geo:51.5008,0.1247;u=-1".parse::<GeoUri>(), Err(GeoUriError::InvalidUncertainty));
+    assert_eq!("geo:51.5008,0.1247,inf".parse::<GeoUri>(), Err(GeoUriError::InvalidCoordinates));
+    assert_eq!("geo:51.5008,0.1247,NaN".parse::<GeoUri>(), Err(GeoUriError::InvalidCoordinates));
+    assert_eq!("geo:NaN,0.1247".parse::<GeoUri>(), Err(GeoUriError::InvalidCoordinates));
+    assert_eq!("geo:51.5008,0.1247;u=inf".parse::<GeoUri>(), Err(GeoUriError::InvalidUncertainty));
+    assert_eq!("geo:51.5008,0.1247;u=NaN".parse::<GeoUri>(), Err(GeoUriError::InvalidUncertainty));
 }
 
 #[test]
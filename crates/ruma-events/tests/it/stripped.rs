@@ -1,8 +1,8 @@
 use assert_matches2::assert_matches;
 use js_int::uint;
-use ruma_common::mxc_uri;
+use ruma_common::{mxc_uri, user_id};
 use ruma_events::{
-    room::{join_rules::JoinRule, topic::RoomTopicEventContent},
+    room::{join_rules::JoinRule, member::MembershipState, topic::RoomTopicEventContent},
     AnyStrippedStateEvent,
 };
 use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
@@ -92,12 +92,55 @@ fn deserialize_stripped_state_events() {
     assert_eq!(image_info.thumbnail_info.unwrap().size, Some(uint!(32)));
 }
 
+#[test]
+fn stripped_state_accessors() {
+    let events = [
+        json!({
+            "type": "m.room.name",
+            "state_key": "",
+            "sender": "@example:localhost",
+            "content": { "name": "Ruma" }
+        }),
+        json!({
+            "type": "m.room.avatar",
+            "state_key": "",
+            "sender": "@example:localhost",
+            "content": { "url": "mxc://example.com/iMag3" }
+        }),
+        json!({
+            "type": "m.room.join_rules",
+            "state_key": "",
+            "sender": "@example:localhost",
+            "content": { "join_rule": "knock" }
+        }),
+        json!({
+            "type": "m.room.member",
+            "state_key": "@alice:localhost",
+            "sender": "@example:localhost",
+            "content": { "membership": "invite" }
+        }),
+    ]
+    .map(|json| from_json_value::<AnyStrippedStateEvent>(json).unwrap());
+
+    assert_eq!(events.iter().find_map(|ev| ev.name()), Some("Ruma"));
+    assert_eq!(
+        events.iter().find_map(|ev| ev.avatar_url()),
+        Some(mxc_uri!("mxc://example.com/iMag3"))
+    );
+    assert_eq!(events.iter().find_map(|ev| ev.join_rule()), Some(&JoinRule::Knock));
+    assert_eq!(events.iter().find_map(|ev| ev.canonical_alias()), None);
+    assert_eq!(
+        events.iter().find_map(|ev| ev.membership(user_id!("@alice:localhost"))),
+        Some(&MembershipState::Invite)
+    );
+    assert_eq!(events.iter().find_map(|ev| ev.membership(user_id!("@example:localhost"))), None);
+}
+
 #[test]
 #[cfg(feature = "unstable-msc4319")]
 fn deserialize_stripped_state_sync_format() {
     use js_int::uint;
-    use ruma_common::{event_id, user_id, MilliSecondsSinceUnixEpoch};
-    use ruma_events::room::member::MembershipState;
+    use ruma_common::{event_id, MilliSecondsSinceUnixEpoch};
 
     let user_id = user_id!("@patrick:localhost");
     let event_id = event_id!("$abcdefgh");
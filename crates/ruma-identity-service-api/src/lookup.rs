@@ -1,6 +1,10 @@
 //! Endpoints to look up Matrix IDs bound to 3PIDs.
 
-use ruma_common::serde::StringEnum;
+use ruma_common::{
+    serde::{base64::UrlSafe, Base64, StringEnum},
+    thirdparty::Medium,
+};
+use sha2::{digest::Digest, Sha256};
 
 use crate::PrivOwnedStr;
 
@@ -27,8 +31,29 @@ pub enum IdentifierHashingAlgorithm {
     _Custom(PrivOwnedStr),
 }
 
+impl IdentifierHashingAlgorithm {
+    /// Format the given third-party identifier to be looked up with this algorithm.
+    ///
+    /// The `address` should already be normalized, e.g. email addresses should be lowercase and
+    /// phone numbers should be in the MSISDN format.
+    ///
+    /// Returns `None` if this algorithm is not supported.
+    pub fn format_address(&self, address: &str, medium: &Medium, pepper: &str) -> Option<String> {
+        match self {
+            Self::Sha256 => {
+                let hash = Sha256::digest(format!("{address} {medium} {pepper}"));
+                Some(Base64::<UrlSafe, _>::new(hash).encode())
+            }
+            Self::None => Some(format!("{address} {medium}")),
+            Self::_Custom(_) => None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use ruma_common::thirdparty::Medium;
+
     use super::IdentifierHashingAlgorithm;
 
     #[test]
@@ -36,4 +61,29 @@ mod tests {
         assert_eq!(IdentifierHashingAlgorithm::from("sha256"), IdentifierHashingAlgorithm::Sha256);
         assert_eq!(IdentifierHashingAlgorithm::from("none"), IdentifierHashingAlgorithm::None);
     }
+
+    #[test]
+    fn format_address() {
+        // Examples from the spec.
+        assert_eq!(
+            IdentifierHashingAlgorithm::Sha256
+                .format_address("alice@example.com", &Medium::Email, "matrixrocks")
+                .unwrap(),
+            "4kenr7N9drpCJ4AfalmlGQVsOn3o2RHjkADUpXJWZUc"
+        );
+        assert_eq!(
+            IdentifierHashingAlgorithm::None
+                .format_address("alice@example.com", &Medium::Email, "matrixrocks")
+                .unwrap(),
+            "alice@example.com email"
+        );
+        assert_eq!(
+            IdentifierHashingAlgorithm::from("sha512").format_address(
+                "alice@example.com",
+                &Medium::Email,
+                "matrixrocks"
+            ),
+            None
+        );
+    }
 }
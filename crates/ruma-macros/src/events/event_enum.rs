@@ -210,7 +210,8 @@ fn expand_deserialize_impl(
     })
 }
 
-/// Implement `From<{event_struct}>` for all the variants of an enum.
+/// Implement `From<{event_struct}>` for all the variants of an enum, and `TryFrom<{enum}>` for the
+/// types of all the variants.
 fn expand_from_impl(
     ty: &Ident,
     event_ty: &[TokenStream],
@@ -229,6 +230,20 @@ fn expand_from_impl(
                     Self::#ident(c)
                 }
             }
+
+            #[allow(unused_qualifications)]
+            #[automatically_derived]
+            #(#attrs)*
+            impl ::std::convert::TryFrom<#ty> for #event_ty {
+                type Error = #ty;
+
+                fn try_from(value: #ty) -> ::std::result::Result<Self, Self::Error> {
+                    match value {
+                        #ty::#ident(c) => ::std::result::Result::Ok(c),
+                        value => ::std::result::Result::Err(value),
+                    }
+                }
+            }
         }
     });
 
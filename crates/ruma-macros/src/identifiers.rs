@@ -2,12 +2,47 @@
 
 use proc_macro2::{Span, TokenStream};
 use quote::{format_ident, quote};
+use ruma_identifiers_validation::{server_signing_key_version, KeyName};
 use syn::{
     parse::{Parse, ParseStream},
     punctuated::Punctuated,
     Fields, ImplGenerics, Index, ItemStruct, LitStr, Path, Token,
 };
 
+/// The key name of a `ServerSigningKeyId`, to validate it with `key_id::validate`.
+///
+/// It is validated like `ServerSigningKeyVersion` in ruma-common.
+pub enum ServerSigningKeyVersionName {}
+
+impl AsRef<str> for ServerSigningKeyVersionName {
+    fn as_ref(&self) -> &str {
+        match *self {}
+    }
+}
+
+impl KeyName for ServerSigningKeyVersionName {
+    fn validate(s: &str) -> Result<(), ruma_identifiers_validation::Error> {
+        server_signing_key_version::validate(s)
+    }
+}
+
+/// The key name of a `DeviceKeyId`, to validate it with `key_id::validate`.
+///
+/// It is validated like `DeviceId` in ruma-common.
+pub enum DeviceIdName {}
+
+impl AsRef<str> for DeviceIdName {
+    fn as_ref(&self) -> &str {
+        match *self {}
+    }
+}
+
+impl KeyName for DeviceIdName {
+    fn validate(_s: &str) -> Result<(), ruma_identifiers_validation::Error> {
+        Ok(())
+    }
+}
+
 pub struct IdentifierInput {
     pub dollar_crate: Path,
     pub id: LitStr,
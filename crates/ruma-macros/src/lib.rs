@@ -14,8 +14,8 @@ use identifiers::expand_id_dst;
 use proc_macro::TokenStream;
 use quote::quote;
 use ruma_identifiers_validation::{
-    base64_public_key, event_id, mxc_uri, room_alias_id, room_id, room_version_id, server_name,
-    server_signing_key_version, user_id,
+    base64_public_key, event_id, key_id, mxc_uri, room_alias_id, room_id, room_version_id,
+    server_name, server_signing_key_version, user_id,
 };
 use syn::{parse_macro_input, DeriveInput, ItemEnum, ItemStruct};
 
@@ -36,7 +36,7 @@ use self::{
         event_enum::{expand_event_enum, EventEnumInput},
         event_enum_from_event::expand_event_enum_from_event,
     },
-    identifiers::IdentifierInput,
+    identifiers::{DeviceIdName, IdentifierInput, ServerSigningKeyVersionName},
     serde::{
         as_str_as_ref_str::expand_as_str_as_ref_str,
         debug_as_ref_str::expand_debug_as_ref_str,
@@ -540,6 +540,35 @@ pub fn server_signing_key_version(input: TokenStream) -> TokenStream {
     output.into()
 }
 
+/// Compile-time checked `ServerSigningKeyId` construction.
+#[proc_macro]
+pub fn server_signing_key_id(input: TokenStream) -> TokenStream {
+    let IdentifierInput { dollar_crate, id } = parse_macro_input!(input as IdentifierInput);
+    assert!(
+        key_id::validate::<ServerSigningKeyVersionName>(&id.value()).is_ok(),
+        "Invalid server_signing_key_id"
+    );
+
+    let output = quote! {
+        <&#dollar_crate::ServerSigningKeyId as ::std::convert::TryFrom<&str>>::try_from(#id).unwrap()
+    };
+
+    output.into()
+}
+
+/// Compile-time checked `DeviceKeyId` construction.
+#[proc_macro]
+pub fn device_key_id(input: TokenStream) -> TokenStream {
+    let IdentifierInput { dollar_crate, id } = parse_macro_input!(input as IdentifierInput);
+    assert!(key_id::validate::<DeviceIdName>(&id.value()).is_ok(), "Invalid device_key_id");
+
+    let output = quote! {
+        <&#dollar_crate::DeviceKeyId as ::std::convert::TryFrom<&str>>::try_from(#id).unwrap()
+    };
+
+    output.into()
+}
+
 /// Compile-time checked `ServerName` construction.
 #[proc_macro]
 pub fn server_name(input: TokenStream) -> TokenStream {
@@ -588,7 +617,7 @@ pub fn base64_public_key(input: TokenStream) -> TokenStream {
     assert!(base64_public_key::validate(&id.value()).is_ok(), "Invalid base64 public key");
 
     let output = quote! {
-        <&#dollar_crate::DeviceKeyId as ::std::convert::TryFrom<&str>>::try_from(#id).unwrap()
+        <&#dollar_crate::Base64PublicKey as ::std::convert::TryFrom<&str>>::try_from(#id).unwrap()
     };
 
     output.into()
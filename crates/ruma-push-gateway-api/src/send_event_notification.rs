@@ -11,7 +11,7 @@ pub mod v1 {
     use ruma_common::{
         api::{request, response, Metadata},
         metadata,
-        push::{PushFormat, Tweak},
+        push::{Action, HttpPusherData, PushFormat, Tweak},
         serde::{JsonObject, StringEnum},
         OwnedEventId, OwnedRoomAliasId, OwnedRoomId, OwnedUserId, SecondsSinceUnixEpoch,
     };
@@ -63,6 +63,21 @@ pub mod v1 {
         pub fn new(rejected: Vec<String>) -> Self {
             Self { rejected }
         }
+
+        /// Whether the given pushkey was rejected.
+        pub fn is_rejected(&self, pushkey: &str) -> bool {
+            self.rejected.iter().any(|rejected| rejected == pushkey)
+        }
+
+        /// Get the devices of the notification that was sent whose pushkey was rejected.
+        ///
+        /// The pushers of these devices should be removed by the homeserver.
+        pub fn rejected_devices<'a>(
+            &'a self,
+            devices: &'a [Device],
+        ) -> impl Iterator<Item = &'a Device> + 'a {
+            devices.iter().filter(|device| self.is_rejected(&device.pushkey))
+        }
     }
 
     /// Type for passing information about a push notification
@@ -137,6 +152,32 @@ pub mod v1 {
         pub fn new(devices: Vec<Device>) -> Self {
             Notification { devices, ..Default::default() }
         }
+
+        /// Keep only the fields of this notification that should be sent to devices using the
+        /// [`PushFormat::EventIdOnly`] format.
+        ///
+        /// The `event_id`, `room_id`, `prio`, `counts` and `devices` fields are kept, the other
+        /// fields are reset to their default value.
+        pub fn into_event_id_only(self) -> Self {
+            let Self { event_id, room_id, prio, counts, devices, .. } = self;
+            Self { event_id, room_id, prio, counts, devices, ..Default::default() }
+        }
+
+        /// Create the notification to send to the given device from this notification.
+        ///
+        /// The devices of the notification are replaced by the given device, and the notification
+        /// is trimmed with [`Notification::into_event_id_only()`] if the device uses the
+        /// [`PushFormat::EventIdOnly`] format.
+        pub fn for_device(self, device: Device) -> Self {
+            let is_event_id_only = device.data.format == Some(PushFormat::EventIdOnly);
+            let notification = Self { devices: vec![device], ..self };
+
+            if is_event_id_only {
+                notification.into_event_id_only()
+            } else {
+                notification
+            }
+        }
     }
 
     /// Type for passing information about notification priority.
@@ -229,6 +270,26 @@ pub mod v1 {
                 tweaks: Vec::new(),
             }
         }
+
+        /// Create a new device for the HTTP pusher with the given app ID, pushkey and data.
+        ///
+        /// The tweaks are set from the `set_tweak` actions of the push rule that matched the event.
+        pub fn from_http_pusher(
+            app_id: String,
+            pushkey: String,
+            data: HttpPusherData,
+            actions: &[Action],
+        ) -> Self {
+            let tweaks = actions
+                .iter()
+                .filter_map(|action| match action {
+                    Action::SetTweak(tweak) => Some(tweak.clone()),
+                    _ => None,
+                })
+                .collect();
+
+            Self { data: data.into(), tweaks, ..Self::new(app_id, pushkey) }
+        }
     }
 
     /// Information for the pusher implementation itself.
@@ -354,7 +415,10 @@ pub mod v1 {
             from_value as from_json_value, json, to_value as to_json_value, Value as JsonValue,
         };
 
-        use super::{Device, Notification, NotificationCounts, NotificationPriority, Tweak};
+        use super::{
+            Action, Device, HttpPusherData, Notification, NotificationCounts, NotificationPriority,
+            PushFormat, Response, Tweak,
+        };
 
         #[test]
         fn serialize_request() {
@@ -424,5 +488,105 @@ pub mod v1 {
 
             assert_eq!(expected, to_json_value(notice).unwrap());
         }
+
+        #[test]
+        fn serialize_event_id_only() {
+            let notice = Notification {
+                event_id: Some(owned_event_id!("$3957tyerfgewrf384")),
+                room_id: Some(owned_room_id!("!slw48wfj34rtnrf:example.com")),
+                event_type: Some(TimelineEventType::RoomMessage),
+                sender: Some(owned_user_id!("@exampleuser:matrix.org")),
+                sender_display_name: Some("Major Tom".to_owned()),
+                content: Some(serde_json::from_str(r#"{"body":"secret"}"#).unwrap()),
+                counts: NotificationCounts::new(uint!(2), uint!(1)),
+                ..Notification::new(vec![Device::new(
+                    "org.matrix.matrixConsole.ios".into(),
+                    "V2h5IG9uIGVhcnRoIGRpZCB5b3UgZGVjb2RlIHRoaXM/".into(),
+                )])
+            };
+
+            assert_eq!(
+                to_json_value(notice.into_event_id_only()).unwrap(),
+                json!({
+                    "event_id": "$3957tyerfgewrf384",
+                    "room_id": "!slw48wfj34rtnrf:example.com",
+                    "counts": {
+                        "unread": 2,
+                        "missed_calls": 1,
+                    },
+                    "devices": [
+                        {
+                            "app_id": "org.matrix.matrixConsole.ios",
+                            "pushkey": "V2h5IG9uIGVhcnRoIGRpZCB5b3UgZGVjb2RlIHRoaXM/",
+                        }
+                    ]
+                })
+            );
+        }
+
+        #[test]
+        fn notification_for_http_pusher() {
+            let actions = [
+                Action::Notify,
+                Action::SetTweak(Tweak::Sound("default".to_owned())),
+                Action::SetTweak(Tweak::Highlight(true)),
+            ];
+            let mut data =
+                HttpPusherData::new("https://push.example.org/_matrix/push/v1/notify".to_owned());
+            data.format = Some(PushFormat::EventIdOnly);
+            let device = Device::from_http_pusher(
+                "org.matrix.matrixConsole.ios".to_owned(),
+                "V2h5IG9uIGVhcnRoIGRpZCB5b3UgZGVjb2RlIHRoaXM/".to_owned(),
+                data,
+                &actions,
+            );
+
+            let notice = Notification {
+                event_id: Some(owned_event_id!("$3957tyerfgewrf384")),
+                room_id: Some(owned_room_id!("!slw48wfj34rtnrf:example.com")),
+                sender: Some(owned_user_id!("@exampleuser:matrix.org")),
+                counts: NotificationCounts::new(uint!(3), uint!(0)),
+                ..Notification::default()
+            };
+
+            assert_eq!(
+                to_json_value(notice.for_device(device)).unwrap(),
+                json!({
+                    "event_id": "$3957tyerfgewrf384",
+                    "room_id": "!slw48wfj34rtnrf:example.com",
+                    "counts": {
+                        "unread": 3,
+                    },
+                    "devices": [
+                        {
+                            "app_id": "org.matrix.matrixConsole.ios",
+                            "pushkey": "V2h5IG9uIGVhcnRoIGRpZCB5b3UgZGVjb2RlIHRoaXM/",
+                            "data": {
+                                "format": "event_id_only",
+                            },
+                            "tweaks": {
+                                "sound": "default",
+                                "highlight": true,
+                            },
+                        }
+                    ]
+                })
+            );
+        }
+
+        #[test]
+        fn rejected_devices() {
+            let devices = [
+                Device::new("org.example.app".to_owned(), "valid".to_owned()),
+                Device::new("org.example.app".to_owned(), "expired".to_owned()),
+            ];
+            let response = Response::new(vec!["expired".to_owned()]);
+
+            assert!(response.is_rejected("expired"));
+            assert!(!response.is_rejected("valid"));
+            let rejected = response.rejected_devices(&devices).collect::<Vec<_>>();
+            assert_eq!(rejected.len(), 1);
+            assert_eq!(rejected[0].pushkey, "expired");
+        }
     }
 }
@@ -0,0 +1,117 @@
+// `cargo bench` works, but if you use `cargo bench -- --save-baseline <name>`
+// or pass any other args to it, it fails with the error
+// `cargo bench unknown option --save-baseline`.
+// To pass args to criterion, use this form
+// `cargo bench --features criterion --bench <name of the bench> -- --save-baseline <name>`.
+
+use std::collections::BTreeMap;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use ruma_common::{
+    canonical_json::{to_canonical_json_string, to_canonical_value},
+    room_version_rules::RoomVersionRules,
+    serde::Base64,
+    CanonicalJsonObject, CanonicalJsonValue,
+};
+use ruma_signatures::{
+    canonical_json, hash_and_sign_event, verify_event, Ed25519KeyPair, PublicKeyMap,
+};
+use serde_json::json;
+
+fn event() -> serde_json::Value {
+    json!({
+        "auth_events": [
+            "$PEnLR9vxVn8YMN9nI6Kd3Q_kPzKz0HtGK4aSwCHkZrA",
+            "$RKJvcd3UNZKmHWB9Ebq2TUyCE33VH4mJzNVkzg2OqEU",
+            "$0n9jpKmNnhyAr8_wrdDuDdX2AZKcXLFRWbIxWbzgAGU"
+        ],
+        "content": {
+            "body": "Hello world! This is a message with some **formatting**.",
+            "format": "org.matrix.custom.html",
+            "formatted_body": "Hello world! This is a message with some <strong>formatting</strong>.",
+            "msgtype": "m.text"
+        },
+        "depth": 42,
+        "origin_server_ts": 1_700_000_000_000_u64,
+        "prev_events": ["$Wv6xVW0yM0sxgqzX2LOZYE_cvuJ0eUJpPyAUZ_bl8pU"],
+        "room_id": "!x:domain",
+        "sender": "@alice:domain",
+        "type": "m.room.message",
+        "unsigned": {
+            "age": 4
+        }
+    })
+}
+
+fn key_pair() -> Ed25519KeyPair {
+    let document = Ed25519KeyPair::generate().unwrap();
+    Ed25519KeyPair::from_der(&document, "1".to_owned()).unwrap()
+}
+
+fn canonical_json_serialization(c: &mut Criterion) {
+    let json = event();
+    let object: CanonicalJsonObject = match CanonicalJsonValue::try_from(json.clone()).unwrap() {
+        CanonicalJsonValue::Object(object) => object,
+        _ => unreachable!(),
+    };
+
+    c.bench_function("serialize `CanonicalJsonObject`", |b| {
+        b.iter(|| {
+            let _ = canonical_json(&object).unwrap();
+        });
+    });
+
+    c.bench_function("serialize `serde_json::Value` to canonical JSON", |b| {
+        b.iter(|| {
+            let _ = to_canonical_json_string(&json).unwrap();
+        });
+    });
+
+    c.bench_function("serialize `serde_json::Value` with `to_canonical_value`", |b| {
+        b.iter(|| {
+            let _ = to_canonical_value(&json).unwrap().to_string();
+        });
+    });
+}
+
+fn sign_event(c: &mut Criterion) {
+    let key_pair = key_pair();
+    let rules = RoomVersionRules::V10;
+    let object: CanonicalJsonObject = match CanonicalJsonValue::try_from(event()).unwrap() {
+        CanonicalJsonValue::Object(object) => object,
+        _ => unreachable!(),
+    };
+
+    c.bench_function("hash and sign event", |b| {
+        b.iter(|| {
+            let mut object = object.clone();
+            hash_and_sign_event("domain", &key_pair, &mut object, &rules.redaction).unwrap();
+        });
+    });
+}
+
+fn verify_signed_event(c: &mut Criterion) {
+    let key_pair = key_pair();
+    let rules = RoomVersionRules::V10;
+    let mut object: CanonicalJsonObject = match CanonicalJsonValue::try_from(event()).unwrap() {
+        CanonicalJsonValue::Object(object) => object,
+        _ => unreachable!(),
+    };
+    hash_and_sign_event("domain", &key_pair, &mut object, &rules.redaction).unwrap();
+
+    let mut public_key_map = PublicKeyMap::new();
+    public_key_map.insert(
+        "domain".to_owned(),
+        BTreeMap::from([("ed25519:1".to_owned(), Base64::new(key_pair.public_key().to_vec()))]),
+    );
+
+    c.bench_function("verify event", |b| {
+        b.iter(|| {
+            let _ = verify_event(&public_key_map, &object, &rules).unwrap();
+        });
+    });
+}
+
+criterion_group!(benches, canonical_json_serialization, sign_event, verify_signed_event);
+
+criterion_main!(benches);
@@ -127,6 +127,10 @@ pub enum VerificationError {
     /// The signature verification failed.
     #[error("Could not verify signature: {0}")]
     Signature(#[source] ed25519_dalek::SignatureError),
+
+    /// None of the signatures could be verified with the given public keys.
+    #[error("Could not find a signature that matches one of the public keys")]
+    NoValidSignature,
 }
 
 /// Errors relating to parsing of all sorts.
@@ -8,13 +8,14 @@ use std::{
 
 use base64::{alphabet, Engine};
 use ruma_common::{
-    canonical_json::{redact, JsonType},
+    canonical_json::{redact, to_canonical_json_string, CanonicalJsonError, JsonType},
     room_version_rules::{EventIdFormatVersion, RedactionRules, RoomVersionRules, SignaturesRules},
     serde::{base64::Standard, Base64},
+    third_party_invite::IdentityServerBase64PublicKey,
     AnyKeyName, CanonicalJsonObject, CanonicalJsonValue, OwnedEventId, OwnedServerName,
     SigningKeyAlgorithm, SigningKeyId, UserId,
 };
-use serde_json::to_string as to_json_string;
+use serde::{Serialize, Serializer};
 use sha2::{digest::Digest, Sha256};
 
 #[cfg(test)]
@@ -113,7 +114,7 @@ where
     let maybe_unsigned_entry = object.remove_entry("unsigned");
 
     // Get the canonical JSON string.
-    let json = to_json_string(object).map_err(JsonError::Serde)?;
+    let json = to_canonical_json(object)?;
 
     // Sign the canonical JSON string.
     let signature = key_pair.sign(json.as_bytes());
@@ -238,6 +239,78 @@ pub fn verify_json(
     Ok(())
 }
 
+/// Uses the public keys of an identity server to verify the `signed` object of a third-party
+/// invite.
+///
+/// This is the object found under `content.third_party_invite.signed` of an `m.room.member` event
+/// for a user that accepted a [third-party invite]. The public keys can be found in the
+/// corresponding `m.room.third_party_invite` event.
+///
+/// Verification succeeds if any signature of any entity in the object can be verified with any of
+/// the public keys. Signatures using an unsupported algorithm and public keys that can't be decoded
+/// are ignored.
+///
+/// # Parameters
+///
+/// * `public_keys`: The public keys of the identity server.
+/// * `signed`: The signed JSON object of the third-party invite.
+///
+/// # Errors
+///
+/// Returns an error if the `signatures` field is missing or has an invalid format, or if no
+/// signature could be verified.
+///
+/// [third-party invite]: https://spec.matrix.org/latest/client-server-api/#third-party-invites
+pub fn verify_third_party_invite<'a>(
+    public_keys: impl IntoIterator<Item = &'a IdentityServerBase64PublicKey>,
+    signed: &CanonicalJsonObject,
+) -> Result<(), Error> {
+    let signature_map = match signed.get("signatures") {
+        Some(CanonicalJsonValue::Object(signatures)) => signatures,
+        Some(_) => return Err(JsonError::not_of_type("signatures", JsonType::Object)),
+        None => return Err(JsonError::field_missing_from_object("signatures")),
+    };
+
+    let public_keys =
+        public_keys.into_iter().filter_map(|key| key.decode().ok()).collect::<Vec<_>>();
+    let canonical_json = canonical_json(signed)?;
+
+    for entity_signatures in signature_map.values() {
+        let CanonicalJsonValue::Object(entity_signatures) = entity_signatures else {
+            return Err(JsonError::not_multiples_of_type("signatures", JsonType::Object));
+        };
+
+        for (key_id, signature) in entity_signatures {
+            let Ok(key_id) = <&SigningKeyId<AnyKeyName>>::try_from(key_id.as_str()) else {
+                continue;
+            };
+            let CanonicalJsonValue::String(signature) = signature else {
+                continue;
+            };
+            let Ok(signature) = Base64::<Standard>::parse(signature) else {
+                continue;
+            };
+
+            let algorithm = key_id.algorithm();
+
+            for public_key in &public_keys {
+                if verify_canonical_json_bytes(
+                    &algorithm,
+                    public_key,
+                    signature.as_bytes(),
+                    canonical_json.as_bytes(),
+                )
+                .is_ok()
+                {
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    Err(VerificationError::NoValidSignature.into())
+}
+
 /// Uses a set of public keys to verify signed canonical JSON bytes for a given entity.
 ///
 /// Implements the algorithm described in the spec for [checking signatures].
@@ -702,13 +775,34 @@ fn canonical_json_with_fields_to_remove(
     object: &CanonicalJsonObject,
     fields: &[&str],
 ) -> Result<String, Error> {
-    let mut owned_object = object.clone();
+    to_canonical_json(&ObjectWithoutFields { object, fields })
+}
 
-    for field in fields {
-        owned_object.remove(*field);
-    }
+/// Serialize the given value as a canonical JSON string.
+fn to_canonical_json<T>(value: &T) -> Result<String, Error>
+where
+    T: Serialize + ?Sized,
+{
+    to_canonical_json_string(value).map_err(|error| match error {
+        CanonicalJsonError::SerDe(error) => JsonError::Serde(error).into(),
+        error => JsonError::Serde(serde::ser::Error::custom(error)).into(),
+    })
+}
+
+/// A JSON object serialized without some of its fields, to avoid cloning it.
+struct ObjectWithoutFields<'a> {
+    object: &'a CanonicalJsonObject,
+    fields: &'a [&'a str],
+}
 
-    to_json_string(&owned_object).map_err(|e| Error::Json(e.into()))
+impl Serialize for ObjectWithoutFields<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer
+            .collect_map(self.object.iter().filter(|(key, _)| !self.fields.contains(&key.as_str())))
+    }
 }
 
 /// Extracts the server names to check signatures for given event.
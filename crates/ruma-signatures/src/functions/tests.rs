@@ -4,16 +4,20 @@ use assert_matches2::assert_matches;
 use ruma_common::{
     room_version_rules::{RoomVersionRules, SignaturesRules},
     serde::Base64,
-    server_name, CanonicalJsonValue, ServerSigningKeyId, SigningKeyAlgorithm,
+    server_name,
+    third_party_invite::IdentityServerBase64PublicKey,
+    CanonicalJsonValue, ServerSigningKeyId, SigningKeyAlgorithm,
 };
 use serde_json::json;
+use sha2::{Digest, Sha256};
 
 use super::{
-    canonical_json, servers_to_check_signatures, sign_json, verify_canonical_json_bytes,
-    verify_event,
+    canonical_json, content_hash, servers_to_check_signatures, sign_json,
+    verify_canonical_json_bytes, verify_event, verify_third_party_invite,
 };
 use crate::{
-    Ed25519KeyPair, Error, KeyPair, PublicKeyMap, PublicKeySet, VerificationError, Verified,
+    Ed25519KeyPair, Error, JsonError, KeyPair, PublicKeyMap, PublicKeySet, VerificationError,
+    Verified,
 };
 
 fn generate_key_pair(name: &str) -> Ed25519KeyPair {
@@ -75,6 +79,35 @@ fn canonical_json_complex() {
     assert_eq!(canonical_json(&object).unwrap(), canonical);
 }
 
+#[test]
+fn canonical_json_same_as_serde_json() {
+    let data = json!({
+        "content": {
+            "body": "Ünïcödé \"quotes\" and\nnewlines \u{1F600}",
+            "nested": { "zeta": [1, -2, null, false], "alpha": {} }
+        },
+        "hashes": { "sha256": "abc" },
+        "origin_server_ts": 1_700_000_000_000_u64,
+        "signatures": { "domain": { "ed25519:1": "sig" } },
+        "unsigned": { "age": 4 },
+        "本": 2,
+        "日": 1
+    });
+
+    let CanonicalJsonValue::Object(object) = CanonicalJsonValue::try_from(data).unwrap() else {
+        unreachable!();
+    };
+
+    let mut expected = object.clone();
+    expected.remove("signatures");
+    expected.remove("unsigned");
+    assert_eq!(canonical_json(&object).unwrap(), serde_json::to_string(&expected).unwrap());
+
+    expected.remove("hashes");
+    let expected_hash = Sha256::digest(serde_json::to_string(&expected).unwrap().as_bytes());
+    assert_eq!(content_hash(&object).unwrap().as_bytes(), expected_hash.as_slice());
+}
+
 #[test]
 fn verify_event_does_not_check_signatures_invite_via_third_party_id() {
     let signed_event = serde_json::from_str(
@@ -673,3 +706,28 @@ fn verify_canonical_json_bytes_wrong_key() {
     .unwrap_err();
     assert_matches!(err, Error::Verification(VerificationError::Signature(_)));
 }
+
+#[test]
+fn verify_third_party_invite_signatures() {
+    let mut signed = serde_json::from_value(json!({
+        "mxid": "@alice:example.org",
+        "token": "abc123",
+    }))
+    .unwrap();
+
+    let key_pair = generate_key_pair("0");
+    sign_json("identity.example.org", &key_pair, &mut signed).unwrap();
+
+    let valid_key = IdentityServerBase64PublicKey::new(&key_pair.public_key());
+    let wrong_key = IdentityServerBase64PublicKey::new(&generate_key_pair("1").public_key());
+    let invalid_key = IdentityServerBase64PublicKey("not base64!".to_owned());
+
+    verify_third_party_invite([&invalid_key, &wrong_key, &valid_key], &signed).unwrap();
+
+    let err = verify_third_party_invite([&invalid_key, &wrong_key], &signed).unwrap_err();
+    assert_matches!(err, Error::Verification(VerificationError::NoValidSignature));
+
+    signed.remove("signatures");
+    let err = verify_third_party_invite([&valid_key], &signed).unwrap_err();
+    assert_matches!(err, Error::Json(JsonError::JsonFieldMissingFromObject(_)));
+}
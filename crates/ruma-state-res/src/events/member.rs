@@ -2,9 +2,12 @@
 
 use std::ops::Deref;
 
-use ruma_common::{serde::from_raw_json_value, CanonicalJsonObject, OwnedUserId};
+use ruma_common::{
+    serde::from_raw_json_value, third_party_invite::IdentityServerBase64PublicKey,
+    CanonicalJsonObject, OwnedUserId,
+};
 use ruma_events::room::member::MembershipState;
-use ruma_signatures::canonical_json;
+use ruma_signatures::{verify_third_party_invite, VerificationError};
 use serde::Deserialize;
 use serde_json::value::RawValue as RawJsonValue;
 
@@ -161,26 +164,22 @@ impl ThirdPartyInvite {
         })
     }
 
-    /// The signatures of the event.
-    pub(crate) fn signatures(&self) -> Result<&CanonicalJsonObject, String> {
-        let Some(signatures_value) = self.signed.get("signatures") else {
-            return Err("missing `signatures` field in `third_party_invite.signed` \
-                        of `m.room.member` event"
-                .into());
-        };
-
-        signatures_value.as_object().ok_or_else(|| {
-            format!(
-                "unexpected format of `signatures` field in `third_party_invite.signed` \
-                 of `m.room.member` event: expected object, got {signatures_value:?}"
-            )
-        })
-    }
-
-    /// The `signed` object as canonical JSON string to verify the signatures.
-    pub(crate) fn signed_canonical_json(&self) -> Result<String, String> {
-        canonical_json(&self.signed).map_err(|error| {
-            format!("invalid `third_party_invite.signed` field in `m.room.member` event: {error}")
+    /// Verify the signatures of the `signed` object with the given public keys.
+    ///
+    /// Succeeds if any signature matches any public key.
+    pub(crate) fn verify_signatures<'a>(
+        &self,
+        public_keys: impl IntoIterator<Item = &'a IdentityServerBase64PublicKey>,
+    ) -> Result<(), String> {
+        verify_third_party_invite(public_keys, &self.signed).map_err(|error| match error {
+            ruma_signatures::Error::Verification(VerificationError::NoValidSignature) => {
+                "no signature on third-party invite matches a public key \
+                 in `m.room.third_party_invite` event"
+                    .to_owned()
+            }
+            error => format!(
+                "invalid `third_party_invite.signed` field in `m.room.member` event: {error}"
+            ),
         })
     }
 }
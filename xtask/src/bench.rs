@@ -17,6 +17,8 @@ pub struct BenchTask {
 pub enum BenchPackage {
     /// Benchmark `ruma-events` crate.
     RumaEvents,
+    /// Benchmark `ruma-signatures` crate.
+    RumaSignatures,
     /// Benchmark `ruma-state-res` crate.
     RumaStateRes,
 }
@@ -24,12 +26,14 @@ pub enum BenchPackage {
 impl BenchPackage {
     /// Get all the possible packages as a list of package arguments (`-p <package>`) for a cargo
     /// command.
-    pub const ALL_PACKAGES_ARGS: &[&str] = &["-p", "ruma-events", "-p", "ruma-state-res"];
+    pub const ALL_PACKAGES_ARGS: &[&str] =
+        &["-p", "ruma-events", "-p", "ruma-signatures", "-p", "ruma-state-res"];
 
     /// Get this package as a package argument (`-p <package>`) for a cargo command.
     pub fn as_package_arg(&self) -> &'static [&'static str] {
         match self {
             Self::RumaEvents => &["-p", "ruma-events"],
+            Self::RumaSignatures => &["-p", "ruma-signatures"],
             Self::RumaStateRes => &["-p", "ruma-state-res"],
         }
     }